@@ -6,6 +6,7 @@ use nokhwa::{
 	utils::{ApiBackend, CameraFormat, FrameFormat, RequestedFormat, RequestedFormatType, Resolution}
 };
 use nvar::{
+	Runtime,
 	ar::{CUDAStream, FaceExpressions, TemporalFilter},
 	cv::{ImageView, PixelFormat}
 };
@@ -24,10 +25,12 @@ fn main() -> anyhow::Result<()> {
 
 	let res = camera.resolution();
 
-	std::env::set_var("NVAR_ROOT", r#"C:\Program Files\NVIDIA Corporation\NVIDIA AR SDK"#);
+	let runtime = Runtime::builder()
+		.with_sdk_root(r#"C:\Program Files\NVIDIA Corporation\NVIDIA AR SDK"#)
+		.build()?;
 
-	let stream = Arc::new(CUDAStream::new()?);
-	let mut nvar = FaceExpressions::builder()?
+	let stream = Arc::new(CUDAStream::new_in(&runtime)?);
+	let mut nvar = FaceExpressions::builder_in(&runtime)?
 		.with_cuda_stream(Arc::clone(&stream))?
 		.with_pose(true)?
 		.with_temporal(TemporalFilter::empty())?
@@ -41,7 +44,7 @@ fn main() -> anyhow::Result<()> {
 		let frame = camera.frame()?;
 		frame.decode_image_to_buffer::<RgbFormat>(&mut frame_buffer)?;
 
-		let image = ImageView::new_rgb(res.width(), res.height(), PixelFormat::RGB, &mut frame_buffer);
+		let image = ImageView::new_rgb_in(&runtime, res.width(), res.height(), PixelFormat::RGB, &mut frame_buffer)?;
 
 		nvar.run(&image)?;
 
@@ -0,0 +1,426 @@
+use std::{
+	any::Any,
+	ffi::{CStr, CString},
+	fmt,
+	marker::PhantomData,
+	pin::Pin,
+	sync::Arc
+};
+
+use super::{CUDAStream, Param, ParamType, Point2D, Point3D, Quaternion, Rect, RenderingParams, SetNvARValue, TrackingBoundingBox, Vector3, Vector3U16, sys};
+use crate::{Result, Runtime, cv::Image};
+
+/// A value that can be owned by a feature and bound to one of its parameters with
+/// [`Feature::bind`](super::Feature::bind).
+///
+/// # Safety
+/// Any pointer passed to the SDK through [`Binding::value`] must remain valid when `self` is moved, i.e. it must point
+/// into a heap allocation owned by `self`.
+pub unsafe trait Binding: Any {
+	type Param: ParamType + ?Sized;
+	type Target: ?Sized;
+
+	fn value(&mut self) -> <Self::Param as ParamType>::Value<'_>;
+
+	fn target(&self) -> &Self::Target;
+}
+
+/// A [`Binding`] whose contents can be modified in place without invalidating the pointer held by the SDK.
+pub trait BindingMut: Binding {
+	fn target_mut(&mut self) -> &mut Self::Target;
+}
+
+/// A typed key to a value owned by a feature, returned by [`Feature::bind`](super::Feature::bind).
+pub struct Bound<B> {
+	pub(crate) index: usize,
+	_marker: PhantomData<fn() -> B>
+}
+
+impl<B> Bound<B> {
+	pub(crate) fn new(index: usize) -> Self {
+		Bound { index, _marker: PhantomData }
+	}
+}
+
+impl<B> Clone for Bound<B> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<B> Copy for Bound<B> {}
+
+impl<B> fmt::Debug for Bound<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("Bound").field(&self.index).finish()
+	}
+}
+
+/// The type-erased storage for a feature's owned bindings.
+pub(crate) struct Bindings(Vec<(&'static CStr, Box<dyn Any>)>);
+
+impl Bindings {
+	pub fn new() -> Self {
+		Bindings(Vec::new())
+	}
+
+	/// Stores `value` as the binding for `param`, dropping the value previously bound to it. The caller must have
+	/// already rebound the parameter in the SDK, so that the old value is no longer referenced when it is dropped.
+	pub fn insert<B: Binding>(&mut self, param: Param<B::Param>, value: B) -> Bound<B> {
+		let value: Box<dyn Any> = Box::new(value);
+		match self.0.iter().position(|(name, _)| *name == param.name()) {
+			Some(index) => {
+				self.0[index].1 = value;
+				Bound::new(index)
+			}
+			None => {
+				self.0.push((param.name(), value));
+				Bound::new(self.0.len() - 1)
+			}
+		}
+	}
+
+	pub fn get<B: Binding>(&self, key: Bound<B>) -> Option<&B> {
+		self.0.get(key.index)?.1.downcast_ref()
+	}
+
+	pub fn get_mut<B: Binding>(&mut self, key: Bound<B>) -> Option<&mut B> {
+		self.0.get_mut(key.index)?.1.downcast_mut()
+	}
+}
+
+macro_rules! impl_vec_binding {
+	($($ty:ty),*) => {
+		$(unsafe impl Binding for Vec<$ty> {
+			type Param = [$ty];
+			type Target = [$ty];
+
+			fn value(&mut self) -> &mut [$ty] {
+				&mut self[..]
+			}
+
+			fn target(&self) -> &[$ty] {
+				self
+			}
+		}
+
+		impl BindingMut for Vec<$ty> {
+			fn target_mut(&mut self) -> &mut [$ty] {
+				self
+			}
+		})*
+	};
+}
+
+impl_vec_binding!(f32, Point2D, Point3D, Quaternion);
+
+macro_rules! impl_box_binding {
+	($($ty:ty),*) => {
+		$(unsafe impl Binding for Box<$ty> {
+			type Param = $ty;
+			type Target = $ty;
+
+			fn value(&mut self) -> Pin<&mut $ty> {
+				Pin::new(&mut **self)
+			}
+
+			fn target(&self) -> &$ty {
+				self
+			}
+		}
+
+		impl BindingMut for Box<$ty> {
+			fn target_mut(&mut self) -> &mut $ty {
+				self
+			}
+		})*
+	};
+}
+
+impl_box_binding!(Quaternion, Vector3, RenderingParams);
+
+unsafe impl Binding for Arc<CUDAStream> {
+	type Param = CUDAStream;
+	type Target = CUDAStream;
+
+	fn value(&mut self) -> &CUDAStream {
+		self
+	}
+
+	fn target(&self) -> &CUDAStream {
+		self
+	}
+}
+
+// The SDK may keep the pointer it is given, so the string is bound as a `CString`, whose buffer survives moves.
+unsafe impl Binding for CString {
+	type Param = str;
+	type Target = CStr;
+
+	fn value(&mut self) -> &CStr {
+		self
+	}
+
+	fn target(&self) -> &CStr {
+		self
+	}
+}
+
+// `Image` keeps its descriptor in a `Box`, so the pointer handed to the SDK survives moves. It is not `BindingMut`,
+// since replacing the image in place would free the descriptor the SDK points to.
+unsafe impl Binding for Image {
+	type Param = Image;
+	type Target = Image;
+
+	fn value(&mut self) -> &mut Image {
+		self
+	}
+
+	fn target(&self) -> &Image {
+		self
+	}
+}
+
+/// An owned buffer for `BoundingBoxes` inputs & outputs.
+pub struct BoundingBoxBuffer {
+	header: Box<sys::NvAR_BBoxes>,
+	boxes: Vec<Rect>
+}
+
+impl BoundingBoxBuffer {
+	/// Creates an empty buffer with room for `max_boxes` boxes, for use as an output.
+	pub fn new(max_boxes: u8) -> Self {
+		let mut boxes = vec![Rect::default(); max_boxes as usize];
+		let header = Box::new(sys::NvAR_BBoxes {
+			boxes: boxes.as_mut_ptr(),
+			max_boxes,
+			num_boxes: 0
+		});
+		BoundingBoxBuffer { header, boxes }
+	}
+
+	/// Creates a buffer holding `boxes`, for use as an input.
+	///
+	/// # Panics
+	/// Panics if there are more than 255 boxes.
+	pub fn from_boxes(mut boxes: Vec<Rect>) -> Self {
+		let len = u8::try_from(boxes.len()).expect("too many bounding boxes");
+		let header = Box::new(sys::NvAR_BBoxes {
+			boxes: boxes.as_mut_ptr(),
+			max_boxes: len,
+			num_boxes: len
+		});
+		BoundingBoxBuffer { header, boxes }
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize {
+		(self.header.num_boxes as usize).min(self.boxes.len())
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl SetNvARValue for &mut BoundingBoxBuffer {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), &mut *self.header) }
+	}
+}
+
+unsafe impl Binding for BoundingBoxBuffer {
+	type Param = sys::NvAR_BBoxes;
+	type Target = [Rect];
+
+	fn value(&mut self) -> &mut BoundingBoxBuffer {
+		self
+	}
+
+	fn target(&self) -> &[Rect] {
+		&self.boxes[..self.len()]
+	}
+}
+
+impl BindingMut for BoundingBoxBuffer {
+	fn target_mut(&mut self) -> &mut [Rect] {
+		let len = self.len();
+		&mut self.boxes[..len]
+	}
+}
+
+/// An owned buffer for the `TrackingBoundingBoxes` output.
+pub struct TrackingBoundingBoxBuffer {
+	header: Box<sys::NvAR_TrackingBBoxes>,
+	boxes: Vec<TrackingBoundingBox>
+}
+
+impl TrackingBoundingBoxBuffer {
+	pub fn new(max_boxes: u8) -> Self {
+		let mut boxes = vec![TrackingBoundingBox::default(); max_boxes as usize];
+		let header = Box::new(sys::NvAR_TrackingBBoxes {
+			boxes: boxes.as_mut_ptr(),
+			num_boxes: 0,
+			max_boxes
+		});
+		TrackingBoundingBoxBuffer { header, boxes }
+	}
+}
+
+impl SetNvARValue for &mut TrackingBoundingBoxBuffer {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), &mut *self.header) }
+	}
+}
+
+unsafe impl Binding for TrackingBoundingBoxBuffer {
+	type Param = sys::NvAR_TrackingBBoxes;
+	type Target = [TrackingBoundingBox];
+
+	fn value(&mut self) -> &mut TrackingBoundingBoxBuffer {
+		self
+	}
+
+	fn target(&self) -> &[TrackingBoundingBox] {
+		&self.boxes[..(self.header.num_boxes as usize).min(self.boxes.len())]
+	}
+}
+
+/// An owned buffer for the `FaceMesh` output.
+pub struct FaceMeshBuffer {
+	header: Box<sys::NvAR_FaceMesh>,
+	vertices: Vec<Vector3>,
+	triangles: Vec<Vector3U16>
+}
+
+impl FaceMeshBuffer {
+	pub fn new(vertex_count: usize, triangle_count: usize) -> Self {
+		let mut vertices = vec![Vector3::default(); vertex_count];
+		let mut triangles = vec![Vector3U16::default(); triangle_count];
+		let header = Box::new(sys::NvAR_FaceMesh {
+			vertices: vertices.as_mut_ptr(),
+			num_vertices: vertex_count,
+			tvi: triangles.as_mut_ptr(),
+			num_triangles: triangle_count
+		});
+		FaceMeshBuffer { header, vertices, triangles }
+	}
+
+	#[inline]
+	pub fn vertices(&self) -> &[Vector3] {
+		&self.vertices[..self.header.num_vertices.min(self.vertices.len())]
+	}
+
+	#[inline]
+	pub fn triangles(&self) -> &[Vector3U16] {
+		&self.triangles[..self.header.num_triangles.min(self.triangles.len())]
+	}
+}
+
+impl SetNvARValue for &mut FaceMeshBuffer {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), &mut *self.header) }
+	}
+}
+
+unsafe impl Binding for FaceMeshBuffer {
+	type Param = sys::NvAR_FaceMesh;
+	type Target = FaceMeshBuffer;
+
+	fn value(&mut self) -> &mut FaceMeshBuffer {
+		self
+	}
+
+	fn target(&self) -> &FaceMeshBuffer {
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{cell::Cell, ffi::CString, rc::Rc};
+
+	use super::{Binding, Bindings, BoundingBoxBuffer, FaceMeshBuffer, TrackingBoundingBoxBuffer};
+	use crate::ar::{Param, Rect};
+
+	/// A binding that records when it is dropped.
+	struct DropFlag(Rc<Cell<bool>>, Vec<f32>);
+
+	impl Drop for DropFlag {
+		fn drop(&mut self) {
+			self.0.set(true);
+		}
+	}
+
+	unsafe impl Binding for DropFlag {
+		type Param = [f32];
+		type Target = [f32];
+
+		fn value(&mut self) -> &mut [f32] {
+			&mut self.1
+		}
+
+		fn target(&self) -> &[f32] {
+			&self.1
+		}
+	}
+
+	#[test]
+	fn insert_replaces_existing_param() {
+		let mut bindings = Bindings::new();
+		let dropped = Rc::new(Cell::new(false));
+		let old = bindings.insert(Param::OUTPUT_LANDMARKS_CONFIDENCE, DropFlag(Rc::clone(&dropped), vec![1.0]));
+		let other = bindings.insert(Param::OUTPUT_EXPRESSION_COEFFICIENTS, vec![2.0f32]);
+		assert_eq!((old.index, other.index), (0, 1));
+		assert!(!dropped.get());
+
+		// Rebinding the same parameter reuses its slot, and only drops the old value once the new one is stored.
+		let new = bindings.insert(Param::OUTPUT_LANDMARKS_CONFIDENCE, vec![3.0f32]);
+		assert_eq!(new.index, old.index);
+		assert!(dropped.get());
+		assert!(bindings.get(old).is_none());
+		assert_eq!(bindings.get(new).map(|v| v.target()), Some(&[3.0][..]));
+		assert_eq!(bindings.get(other).map(|v| v.target()), Some(&[2.0][..]));
+	}
+
+	#[test]
+	fn bounding_boxes_clamp_to_capacity() {
+		let mut buffer = BoundingBoxBuffer::new(4);
+		assert!(buffer.is_empty());
+		buffer.header.num_boxes = 2;
+		assert_eq!(buffer.target().len(), 2);
+		buffer.header.num_boxes = 10;
+		assert_eq!(buffer.len(), 4);
+
+		let buffer = BoundingBoxBuffer::from_boxes(vec![Rect::default(); 3]);
+		assert_eq!((buffer.header.max_boxes, buffer.len()), (3, 3));
+		assert_eq!(buffer.header.boxes.cast_const(), buffer.boxes.as_ptr());
+	}
+
+	#[test]
+	fn tracking_boxes_clamp_to_capacity() {
+		let mut buffer = TrackingBoundingBoxBuffer::new(2);
+		assert!(buffer.target().is_empty());
+		buffer.header.num_boxes = 5;
+		assert_eq!(buffer.target().len(), 2);
+	}
+
+	#[test]
+	fn face_mesh_clamps_to_capacity() {
+		let mut buffer = FaceMeshBuffer::new(6, 4);
+		assert_eq!((buffer.vertices().len(), buffer.triangles().len()), (6, 4));
+		buffer.header.num_vertices = 3;
+		buffer.header.num_triangles = 100;
+		assert_eq!((buffer.vertices().len(), buffer.triangles().len()), (3, 4));
+	}
+
+	#[test]
+	fn string_pointer_survives_moves() {
+		let mut string = CString::new("models").unwrap();
+		let ptr = string.value().as_ptr();
+		let moved = Box::new(string);
+		assert_eq!(moved.target().as_ptr(), ptr);
+		assert_eq!(moved.target(), c"models");
+	}
+}
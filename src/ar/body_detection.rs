@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use super::{BoundingBoxes, CUDAStream, FeatureBase, InputImage, Param, sys};
+use crate::{Result, Runtime, Version, cv::Image};
+
+const MIN_SDK_VERSION: Version = Version::new(0, 7, 0);
+
+pub struct BodyDetectionBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>,
+	max_boxes: u8
+}
+
+impl_builder_common!(BodyDetectionBuilder);
+
+impl BodyDetectionBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		runtime.require_version(MIN_SDK_VERSION)?;
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_BodyDetection)?;
+		Ok(BodyDetectionBuilder { feature, stream: None, max_boxes: 25 })
+	}
+
+	/// Enables temporal smoothing of the detected boxes across frames of a video stream.
+	pub fn with_temporal(mut self, temporal: bool) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, u32::from(temporal))?;
+		Ok(self)
+	}
+
+	/// Sets the maximum number of people that can be detected in a single frame. Defaults to 25.
+	pub fn with_max_boxes(mut self, max_boxes: u8) -> Self {
+		self.max_boxes = max_boxes;
+		self
+	}
+
+	pub fn load(mut self) -> Result<BodyDetection> {
+		self.feature.load()?;
+		BodyDetection::new(self.feature, self.stream, self.max_boxes)
+	}
+}
+
+pub struct BodyDetection {
+	feature: FeatureBase,
+	input_image: InputImage,
+	bounding_boxes: BoundingBoxes,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(BodyDetection);
+
+impl BodyDetection {
+	pub fn builder() -> Result<BodyDetectionBuilder> {
+		BodyDetectionBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<BodyDetectionBuilder> {
+		BodyDetectionBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>, max_boxes: u8) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, max_boxes)?;
+
+		let input_image = InputImage::new(&mut feature)?;
+
+		Ok(Self {
+			feature,
+			input_image,
+			bounding_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, temporal: bool) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, u32::from(temporal))?;
+		Ok(())
+	}
+
+	/// Detects people in `image`, returning the number of people found.
+	pub fn run(&mut self, image: &Image) -> Result<usize> {
+		self.input_image.upload(&mut self.feature, image, self.stream.as_deref())?;
+		self.feature.run()?;
+		Ok(self.bounding_boxes.len(&self.feature))
+	}
+}
@@ -0,0 +1,360 @@
+use std::{num::NonZeroU8, slice, sync::Arc};
+
+use super::{Bound, BoundingBoxes, CUDAStream, FeatureBase, InputImage, Param, Point2D, Point3D, Quaternion, Rect, TrackingBoundingBoxBuffer, sys};
+use crate::{Result, Runtime, Version, cv::Image};
+
+const MIN_SDK_VERSION: Version = Version::new(0, 7, 0);
+
+/// The keypoints tracked by [`BodyPoseEstimation`], in the order the SDK outputs them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum BodyJoint {
+	Pelvis,
+	LeftHip,
+	RightHip,
+	Torso,
+	LeftKnee,
+	RightKnee,
+	Neck,
+	LeftAnkle,
+	RightAnkle,
+	LeftBigToe,
+	RightBigToe,
+	LeftSmallToe,
+	RightSmallToe,
+	LeftHeel,
+	RightHeel,
+	Nose,
+	LeftEye,
+	RightEye,
+	LeftEar,
+	RightEar,
+	LeftShoulder,
+	RightShoulder,
+	LeftElbow,
+	RightElbow,
+	LeftWrist,
+	RightWrist,
+	LeftPinkyKnuckle,
+	RightPinkyKnuckle,
+	LeftMiddleTip,
+	RightMiddleTip,
+	LeftIndexKnuckle,
+	RightIndexKnuckle,
+	LeftThumbTip,
+	RightThumbTip
+}
+
+impl BodyJoint {
+	pub const ALL: [BodyJoint; 34] = [
+		BodyJoint::Pelvis,
+		BodyJoint::LeftHip,
+		BodyJoint::RightHip,
+		BodyJoint::Torso,
+		BodyJoint::LeftKnee,
+		BodyJoint::RightKnee,
+		BodyJoint::Neck,
+		BodyJoint::LeftAnkle,
+		BodyJoint::RightAnkle,
+		BodyJoint::LeftBigToe,
+		BodyJoint::RightBigToe,
+		BodyJoint::LeftSmallToe,
+		BodyJoint::RightSmallToe,
+		BodyJoint::LeftHeel,
+		BodyJoint::RightHeel,
+		BodyJoint::Nose,
+		BodyJoint::LeftEye,
+		BodyJoint::RightEye,
+		BodyJoint::LeftEar,
+		BodyJoint::RightEar,
+		BodyJoint::LeftShoulder,
+		BodyJoint::RightShoulder,
+		BodyJoint::LeftElbow,
+		BodyJoint::RightElbow,
+		BodyJoint::LeftWrist,
+		BodyJoint::RightWrist,
+		BodyJoint::LeftPinkyKnuckle,
+		BodyJoint::RightPinkyKnuckle,
+		BodyJoint::LeftMiddleTip,
+		BodyJoint::RightMiddleTip,
+		BodyJoint::LeftIndexKnuckle,
+		BodyJoint::RightIndexKnuckle,
+		BodyJoint::LeftThumbTip,
+		BodyJoint::RightThumbTip
+	];
+
+	/// The index of this joint in the keypoint arrays.
+	#[inline]
+	pub const fn index(self) -> usize {
+		self as usize
+	}
+
+	#[inline]
+	pub fn from_index(index: usize) -> Option<Self> {
+		Self::ALL.get(index).copied()
+	}
+}
+
+/// The pose of a single person tracked by [`BodyPoseEstimation`] in multi-person mode.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackedPerson<'f> {
+	pub tracking_id: u16,
+	pub bounding_box: &'f Rect,
+	pub keypoints: &'f [Point2D],
+	pub keypoints_3d: &'f [Point3D],
+	pub keypoint_confidence: &'f [f32],
+	pub joint_angles: &'f [Quaternion]
+}
+
+impl TrackedPerson<'_> {
+	pub fn keypoint(&self, joint: BodyJoint) -> Option<&Point2D> {
+		self.keypoints.get(joint.index())
+	}
+
+	pub fn keypoint_3d(&self, joint: BodyJoint) -> Option<&Point3D> {
+		self.keypoints_3d.get(joint.index())
+	}
+
+	pub fn joint_angle(&self, joint: BodyJoint) -> Option<&Quaternion> {
+		self.joint_angles.get(joint.index())
+	}
+}
+
+pub struct BodyPoseEstimationBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>,
+	max_boxes: u8,
+	max_targets: Option<u8>
+}
+
+impl_builder_common!(BodyPoseEstimationBuilder);
+
+impl BodyPoseEstimationBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		runtime.require_version(MIN_SDK_VERSION)?;
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_BodyPoseEstimation)?;
+		Ok(BodyPoseEstimationBuilder {
+			feature,
+			stream: None,
+			max_boxes: 25,
+			max_targets: None
+		})
+	}
+
+	/// Enables temporal smoothing of the keypoints across frames of a video stream.
+	pub fn with_temporal(mut self, temporal: bool) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, u32::from(temporal))?;
+		Ok(self)
+	}
+
+	/// Sets the focal length of the camera in pixels, used to estimate the 3D keypoints. Defaults to 800.
+	pub fn with_focal_length(mut self, focal_length: f32) -> Result<Self> {
+		self.feature.set(Param::CONFIG_FOCAL_LENGTH, focal_length)?;
+		Ok(self)
+	}
+
+	/// Sets the maximum number of people that can be detected in a single frame. Defaults to 25.
+	pub fn with_max_boxes(mut self, max_boxes: u8) -> Self {
+		self.max_boxes = max_boxes;
+		self
+	}
+
+	/// Enables multi-person mode, tracking up to `max_targets` people across frames. Each person keeps the same
+	/// [tracking ID](TrackedPerson::tracking_id) for as long as they remain in view.
+	pub fn with_multi_person_tracking(mut self, max_targets: NonZeroU8) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TRACK_PEOPLE, 1u32)?;
+		self.feature.set(Param::CONFIG_MAX_TARGETS_TRACKED, u32::from(max_targets.get()))?;
+		self.max_targets = Some(max_targets.get());
+		Ok(self)
+	}
+
+	/// Sets the number of frames a person who is no longer detected keeps their tracking ID for. Only relevant in
+	/// [multi-person mode](BodyPoseEstimationBuilder::with_multi_person_tracking).
+	pub fn with_shadow_tracking_age(mut self, frames: u32) -> Result<Self> {
+		self.feature.set(Param::CONFIG_SHADOW_TRACKING_AGE, frames)?;
+		Ok(self)
+	}
+
+	/// Sets the number of frames a newly detected person must be seen for before they are assigned a tracking ID. Only
+	/// relevant in [multi-person mode](BodyPoseEstimationBuilder::with_multi_person_tracking).
+	pub fn with_probation_age(mut self, frames: u32) -> Result<Self> {
+		self.feature.set(Param::CONFIG_PROBATION_AGE, frames)?;
+		Ok(self)
+	}
+
+	pub fn load(mut self) -> Result<BodyPoseEstimation> {
+		self.feature.load()?;
+		BodyPoseEstimation::new(self.feature, self.stream, self.max_boxes, self.max_targets)
+	}
+}
+
+pub struct BodyPoseEstimation {
+	feature: FeatureBase,
+	input_image: InputImage,
+	num_keypoints: usize,
+	reference_pose: Vec<Point3D>,
+	keypoints: Bound<Vec<Point2D>>,
+	keypoints_3d: Bound<Vec<Point3D>>,
+	keypoint_confidence: Bound<Vec<f32>>,
+	joint_angles: Bound<Vec<Quaternion>>,
+	bounding_boxes: BoundingBoxes,
+	tracking_boxes: Option<Bound<TrackingBoundingBoxBuffer>>,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(BodyPoseEstimation);
+
+impl BodyPoseEstimation {
+	pub fn builder() -> Result<BodyPoseEstimationBuilder> {
+		BodyPoseEstimationBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<BodyPoseEstimationBuilder> {
+		BodyPoseEstimationBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>, max_boxes: u8, max_targets: Option<u8>) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, max_boxes)?;
+
+		let num_keypoints = feature.get(Param::CONFIG_NUM_KEY_POINTS)? as usize;
+
+		// The reference pose is owned by the SDK; copy it out so it can outlive the borrow of the feature.
+		let reference_pose = feature.get_object(Param::CONFIG_REFERENCE_POSE)?;
+		let reference_pose = if reference_pose.is_null() {
+			Vec::new()
+		} else {
+			unsafe { slice::from_raw_parts(reference_pose, num_keypoints) }.to_vec()
+		};
+
+		let tracking_boxes = match max_targets {
+			Some(max_targets) => Some(feature.bind(Param::OUTPUT_TRACKING_BOUNDING_BOXES, TrackingBoundingBoxBuffer::new(max_targets))?),
+			None => None
+		};
+
+		// In multi-person mode, the SDK writes one block of `num_keypoints` entries per tracked person.
+		let num_outputs = num_keypoints * max_targets.map_or(1, usize::from);
+
+		let keypoints = feature.bind(Param::OUTPUT_KEY_POINTS, vec![Point2D::default(); num_outputs])?;
+		let keypoints_3d = feature.bind(Param::OUTPUT_KEY_POINTS_3D, vec![Point3D::default(); num_outputs])?;
+		let keypoint_confidence = feature.bind(Param::OUTPUT_KEY_POINTS_CONFIDENCE, vec![0.0f32; num_outputs])?;
+		let joint_angles = feature.bind(Param::OUTPUT_JOINT_ANGLES, vec![Quaternion::default(); num_outputs])?;
+
+		let input_image = InputImage::new(&mut feature)?;
+
+		Ok(Self {
+			feature,
+			input_image,
+			num_keypoints,
+			reference_pose,
+			keypoints,
+			keypoints_3d,
+			keypoint_confidence,
+			joint_angles,
+			bounding_boxes,
+			tracking_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, temporal: bool) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, u32::from(temporal))?;
+		Ok(())
+	}
+
+	/// Estimates the pose of the most prominent person in `image`, returning the number of people detected.
+	pub fn run(&mut self, image: &Image) -> Result<usize> {
+		self.input_image.upload(&mut self.feature, image, self.stream.as_deref())?;
+		self.feature.run()?;
+		Ok(self.bounding_boxes.len(&self.feature))
+	}
+
+	/// The number of keypoints output per person, normally 34.
+	pub fn num_keypoints(&self) -> usize {
+		self.num_keypoints
+	}
+
+	/// The 3D keypoints of the neutral pose the joint angles are relative to.
+	pub fn reference_pose(&self) -> &[Point3D] {
+		&self.reference_pose
+	}
+
+	/// The keypoints in image space, indexed by [`BodyJoint::index`]. In multi-person mode, these are the keypoints of
+	/// the first tracked person; use [`BodyPoseEstimation::people`] instead.
+	pub fn keypoints(&self) -> &[Point2D] {
+		&self.feature.bound(self.keypoints)[..self.num_keypoints]
+	}
+
+	/// The keypoints in camera space, indexed by [`BodyJoint::index`].
+	pub fn keypoints_3d(&self) -> &[Point3D] {
+		&self.feature.bound(self.keypoints_3d)[..self.num_keypoints]
+	}
+
+	pub fn keypoint_confidence(&self) -> &[f32] {
+		&self.feature.bound(self.keypoint_confidence)[..self.num_keypoints]
+	}
+
+	/// The rotation of each joint relative to the [reference pose](BodyPoseEstimation::reference_pose).
+	pub fn joint_angles(&self) -> &[Quaternion] {
+		&self.feature.bound(self.joint_angles)[..self.num_keypoints]
+	}
+
+	/// The people tracked in the last frame, in [multi-person
+	/// mode](BodyPoseEstimationBuilder::with_multi_person_tracking). Returns an empty list if multi-person mode is
+	/// disabled.
+	pub fn people(&self) -> Vec<TrackedPerson<'_>> {
+		let Some(tracking_boxes) = self.tracking_boxes else {
+			return Vec::new();
+		};
+		let n = self.num_keypoints;
+		let (keypoints, keypoints_3d) = (self.feature.bound(self.keypoints), self.feature.bound(self.keypoints_3d));
+		let (keypoint_confidence, joint_angles) = (self.feature.bound(self.keypoint_confidence), self.feature.bound(self.joint_angles));
+		self.feature
+			.bound(tracking_boxes)
+			.iter()
+			.enumerate()
+			.map(|(i, tracked)| {
+				let range = i * n..(i + 1) * n;
+				TrackedPerson {
+					tracking_id: tracked.tracking_id,
+					bounding_box: &tracked.bbox,
+					keypoints: &keypoints[range.clone()],
+					keypoints_3d: &keypoints_3d[range.clone()],
+					keypoint_confidence: &keypoint_confidence[range.clone()],
+					joint_angles: &joint_angles[range]
+				}
+			})
+			.collect()
+	}
+
+	/// The person with the given tracking ID, if they were tracked in the last frame.
+	pub fn person(&self, tracking_id: u16) -> Option<TrackedPerson<'_>> {
+		self.people().into_iter().find(|person| person.tracking_id == tracking_id)
+	}
+
+	pub fn keypoint(&self, joint: BodyJoint) -> Option<&Point2D> {
+		self.keypoints().get(joint.index())
+	}
+
+	pub fn keypoint_3d(&self, joint: BodyJoint) -> Option<&Point3D> {
+		self.keypoints_3d().get(joint.index())
+	}
+
+	pub fn joint_angle(&self, joint: BodyJoint) -> Option<&Quaternion> {
+		self.joint_angles().get(joint.index())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::BodyJoint;
+
+	#[test]
+	fn joints_are_in_sdk_order() {
+		for (index, joint) in BodyJoint::ALL.iter().enumerate() {
+			assert_eq!(joint.index(), index);
+			assert_eq!(BodyJoint::from_index(index), Some(*joint));
+		}
+		assert_eq!(BodyJoint::ALL[0], BodyJoint::Pelvis);
+		assert_eq!(BodyJoint::from_index(BodyJoint::ALL.len()), None);
+	}
+}
@@ -0,0 +1,130 @@
+use std::ffi::{CStr, CString};
+
+use super::{CUDAStream, FeatureBase, Param, sys};
+use crate::{Error, NvError, Result};
+
+/// The value of a config parameter, as reported by [`Feature::describe`](super::Feature::describe).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+	U32(u32),
+	F32(f32),
+	String(String),
+	/// The raw CUDA stream the feature runs on, which is null for the default stream.
+	CUDAStream(sys::CUstream),
+	/// The parameter is a string that has not been set, e.g. a model directory when the default models are used.
+	Unset,
+	/// The feature does not support this parameter.
+	Unsupported
+}
+
+/// A snapshot of a feature's config parameters, for debugging.
+#[derive(Debug, Clone)]
+pub struct FeatureDescription {
+	pub feature_id: CString,
+	pub config: Vec<(&'static CStr, ConfigValue)>
+}
+
+impl FeatureDescription {
+	pub fn get(&self, name: &CStr) -> Option<&ConfigValue> {
+		self.config.iter().find(|(n, _)| *n == name).map(|(_, value)| value)
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Key {
+	U32(Param<u32>),
+	F32(Param<f32>),
+	String(Param<str>),
+	CUDAStream(Param<CUDAStream>)
+}
+
+impl Key {
+	fn name(self) -> &'static CStr {
+		match self {
+			Key::U32(p) => p.name(),
+			Key::F32(p) => p.name(),
+			Key::String(p) => p.name(),
+			Key::CUDAStream(p) => p.name()
+		}
+	}
+}
+
+const COMMON_KEYS: &[Key] = &[
+	Key::String(Param::CONFIG_FEATURE_DESCRIPTION),
+	Key::String(Param::CONFIG_MODEL_DIR),
+	Key::CUDAStream(Param::CONFIG_CUDA_STREAM),
+	Key::U32(Param::CONFIG_TEMPORAL),
+	Key::U32(Param::CONFIG_MODE),
+	Key::U32(Param::CONFIG_USE_CUDA_GRAPH)
+];
+
+fn feature_keys(feature_id: &CStr) -> &'static [Key] {
+	if feature_id == sys::NvAR_Feature_LandmarkDetection {
+		&[Key::U32(Param::CONFIG_LANDMARKS_SIZE)]
+	} else if feature_id == sys::NvAR_Feature_Face3DReconstruction {
+		&[
+			Key::U32(Param::CONFIG_LANDMARKS_SIZE),
+			Key::U32(Param::CONFIG_EXPRESSION_COUNT),
+			Key::U32(Param::CONFIG_SHAPE_EIGEN_VALUE_COUNT),
+			Key::U32(Param::CONFIG_VERTEX_COUNT),
+			Key::U32(Param::CONFIG_TRIANGLE_COUNT),
+			Key::String(Param::CONFIG_FACE_MODEL)
+		]
+	} else if feature_id == sys::NvAR_Feature_FaceExpressions {
+		&[
+			Key::U32(Param::CONFIG_LANDMARKS_SIZE),
+			Key::U32(Param::CONFIG_EXPRESSION_COUNT),
+			Key::U32(Param::CONFIG_POSE_MODE),
+			Key::U32(Param::CONFIG_ENABLE_CHEEK_PUFF)
+		]
+	} else if feature_id == sys::NvAR_Feature_BodyPoseEstimation {
+		&[
+			Key::U32(Param::CONFIG_NUM_KEY_POINTS),
+			Key::F32(Param::CONFIG_FOCAL_LENGTH),
+			Key::U32(Param::CONFIG_TRACK_PEOPLE),
+			Key::U32(Param::CONFIG_SHADOW_TRACKING_AGE),
+			Key::U32(Param::CONFIG_PROBATION_AGE),
+			Key::U32(Param::CONFIG_MAX_TARGETS_TRACKED)
+		]
+	} else if feature_id == sys::NvAR_Feature_GazeRedirection {
+		&[
+			Key::U32(Param::CONFIG_LANDMARKS_SIZE),
+			Key::U32(Param::CONFIG_GAZE_REDIRECT),
+			Key::U32(Param::CONFIG_EYE_SIZE_SENSITIVITY)
+		]
+	} else {
+		&[]
+	}
+}
+
+pub(crate) fn describe(feature: &FeatureBase) -> Result<FeatureDescription> {
+	let config = COMMON_KEYS
+		.iter()
+		.chain(feature_keys(feature.id()))
+		.map(|&key| {
+			let value = match key {
+				Key::U32(p) => feature.get(p).map(ConfigValue::U32),
+				Key::F32(p) => feature.get(p).map(ConfigValue::F32),
+				Key::String(p) => feature.get_str(p).map(|s| match s {
+					Some(s) => ConfigValue::String(s.to_string_lossy().into_owned()),
+					None => ConfigValue::Unset
+				}),
+				Key::CUDAStream(p) => feature.get_cuda_stream(p).map(ConfigValue::CUDAStream)
+			};
+			match value {
+				Ok(value) => Ok((key.name(), value)),
+				// The SDK rejects keys a feature doesn't know, or that don't apply to its type, with these statuses; anything
+				// else, like a CUDA or initialization failure, is a real error.
+				Err(Error::Nv {
+					status: NvError::Selector | NvError::Parameter,
+					..
+				}) => Ok((key.name(), ConfigValue::Unsupported)),
+				Err(e) => Err(e)
+			}
+		})
+		.collect::<Result<_>>()?;
+	Ok(FeatureDescription {
+		feature_id: feature.id().to_owned(),
+		config
+	})
+}
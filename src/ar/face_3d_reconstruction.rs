@@ -0,0 +1,174 @@
+use std::{ffi::CString, sync::Arc};
+
+use super::{
+	Bound, BoundingBoxes, CUDAStream, FaceMeshBuffer, FeatureBase, InputImage, LandmarksSize, Param, Point2D, Quaternion, RenderingParams, TemporalFilter,
+	Vector3, Vector3U16, sys
+};
+use crate::{Result, Runtime, cv::Image};
+
+/// A view of the face mesh fitted by [`Face3DReconstruction`].
+#[derive(Debug, Clone, Copy)]
+pub struct FaceMesh<'f> {
+	vertices: &'f [Vector3],
+	triangles: &'f [Vector3U16]
+}
+
+impl<'f> FaceMesh<'f> {
+	#[inline]
+	pub fn vertices(&self) -> &'f [Vector3] {
+		self.vertices
+	}
+
+	/// Each triangle holds the indices of its three vertices in [`FaceMesh::vertices`].
+	#[inline]
+	pub fn triangles(&self) -> &'f [Vector3U16] {
+		self.triangles
+	}
+}
+
+pub struct Face3DReconstructionBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_builder_common!(Face3DReconstructionBuilder);
+
+impl Face3DReconstructionBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_Face3DReconstruction)?;
+		Ok(Face3DReconstructionBuilder { feature, stream: None })
+	}
+
+	pub fn with_temporal(mut self, temporal_filter: TemporalFilter) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, temporal_filter.bits())?;
+		Ok(self)
+	}
+
+	pub fn with_landmarks_size(mut self, size: LandmarksSize) -> Result<Self> {
+		self.feature.set(Param::CONFIG_LANDMARKS_SIZE, size as u32)?;
+		Ok(self)
+	}
+
+	/// Sets the face model file to fit, relative to the model directory, e.g. `face_model2.nvf`.
+	pub fn with_face_model(mut self, model: &str) -> Result<Self> {
+		self.feature.bind(Param::CONFIG_FACE_MODEL, CString::new(model)?)?;
+		Ok(self)
+	}
+
+	pub fn load(mut self) -> Result<Face3DReconstruction> {
+		self.feature.load()?;
+		Face3DReconstruction::new(self.feature, self.stream)
+	}
+}
+
+pub struct Face3DReconstruction {
+	feature: FeatureBase,
+	input_image: InputImage,
+	mesh: Bound<FaceMeshBuffer>,
+	rendering_params: Bound<Box<RenderingParams>>,
+	shape_eigenvalues: Bound<Vec<f32>>,
+	expression_coefficients: Bound<Vec<f32>>,
+	pose_rotation: Bound<Box<Quaternion>>,
+	landmarks: Bound<Vec<Point2D>>,
+	landmark_confidence: Bound<Vec<f32>>,
+	bounding_boxes: BoundingBoxes,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(Face3DReconstruction);
+
+impl Face3DReconstruction {
+	pub fn builder() -> Result<Face3DReconstructionBuilder> {
+		Face3DReconstructionBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<Face3DReconstructionBuilder> {
+		Face3DReconstructionBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, 25)?;
+
+		let vertex_count = feature.get(Param::CONFIG_VERTEX_COUNT)? as usize;
+		let triangle_count = feature.get(Param::CONFIG_TRIANGLE_COUNT)? as usize;
+		let mesh = feature.bind(Param::OUTPUT_FACE_MESH, FaceMeshBuffer::new(vertex_count, triangle_count))?;
+		let rendering_params = feature.bind(Param::OUTPUT_RENDERING_PARAMS, Box::new(RenderingParams::default()))?;
+
+		let shape_eigenvalue_count = feature.get(Param::CONFIG_SHAPE_EIGEN_VALUE_COUNT)? as usize;
+		let shape_eigenvalues = feature.bind(Param::OUTPUT_SHAPE_EIGEN_VALUES, vec![0.0; shape_eigenvalue_count])?;
+
+		let expr_count = feature.get(Param::CONFIG_EXPRESSION_COUNT)? as usize;
+		let expression_coefficients = feature.bind(Param::OUTPUT_EXPRESSION_COEFFICIENTS, vec![0.0; expr_count])?;
+
+		let landmarks_size = feature.get(Param::CONFIG_LANDMARKS_SIZE)? as usize;
+		let landmarks = feature.bind(Param::OUTPUT_LANDMARKS, vec![Point2D::default(); landmarks_size])?;
+		let landmark_confidence = feature.bind(Param::OUTPUT_LANDMARKS_CONFIDENCE, vec![0.0f32; landmarks_size])?;
+
+		let pose_rotation = feature.bind(Param::OUTPUT_POSE, Box::new(Quaternion::default()))?;
+
+		let input_image = InputImage::new(&mut feature)?;
+		feature.set(Param::INPUT_WIDTH, 32u32)?;
+		feature.set(Param::INPUT_HEIGHT, 32u32)?;
+
+		Ok(Self {
+			feature,
+			input_image,
+			mesh,
+			rendering_params,
+			shape_eigenvalues,
+			expression_coefficients,
+			pose_rotation,
+			landmarks,
+			landmark_confidence,
+			bounding_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, filter: TemporalFilter) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, filter.bits())?;
+		Ok(())
+	}
+
+	pub fn run(&mut self, image: &Image) -> Result<()> {
+		if self.input_image.upload(&mut self.feature, image, self.stream.as_deref())? {
+			self.feature.set(Param::INPUT_WIDTH, image.width())?;
+			self.feature.set(Param::INPUT_HEIGHT, image.height())?;
+		}
+		self.feature.run()?;
+		Ok(())
+	}
+
+	pub fn mesh(&self) -> FaceMesh<'_> {
+		let mesh = self.feature.bound(self.mesh);
+		FaceMesh {
+			vertices: mesh.vertices(),
+			triangles: mesh.triangles()
+		}
+	}
+
+	pub fn rendering_params(&self) -> &RenderingParams {
+		self.feature.bound(self.rendering_params)
+	}
+
+	/// The identity coefficients of the fitted face model.
+	pub fn shape_eigenvalues(&self) -> &[f32] {
+		self.feature.bound(self.shape_eigenvalues)
+	}
+
+	pub fn expressions(&self) -> &[f32] {
+		self.feature.bound(self.expression_coefficients)
+	}
+
+	pub fn rotation(&self) -> &Quaternion {
+		self.feature.bound(self.pose_rotation)
+	}
+
+	pub fn landmarks(&self) -> &[Point2D] {
+		self.feature.bound(self.landmarks)
+	}
+
+	pub fn landmark_confidence(&self) -> &[f32] {
+		self.feature.bound(self.landmark_confidence)
+	}
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use super::{BoundingBoxes, CUDAStream, FeatureBase, InputImage, Param, TemporalFilter, sys};
+use crate::{Result, Runtime, cv::Image};
+
+pub struct FaceBoxDetectionBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>,
+	max_boxes: u8
+}
+
+impl_builder_common!(FaceBoxDetectionBuilder);
+
+impl FaceBoxDetectionBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_FaceBoxDetection)?;
+		Ok(FaceBoxDetectionBuilder { feature, stream: None, max_boxes: 25 })
+	}
+
+	/// Only [`TemporalFilter::FACE_BOX`] is relevant to this feature.
+	pub fn with_temporal(mut self, temporal_filter: TemporalFilter) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, temporal_filter.bits())?;
+		Ok(self)
+	}
+
+	/// Sets the maximum number of faces that can be detected in a single frame. Defaults to 25.
+	pub fn with_max_boxes(mut self, max_boxes: u8) -> Self {
+		self.max_boxes = max_boxes;
+		self
+	}
+
+	pub fn load(mut self) -> Result<FaceBoxDetection> {
+		self.feature.load()?;
+		FaceBoxDetection::new(self.feature, self.stream, self.max_boxes)
+	}
+}
+
+pub struct FaceBoxDetection {
+	feature: FeatureBase,
+	input_image: InputImage,
+	bounding_boxes: BoundingBoxes,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(FaceBoxDetection);
+
+impl FaceBoxDetection {
+	pub fn builder() -> Result<FaceBoxDetectionBuilder> {
+		FaceBoxDetectionBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<FaceBoxDetectionBuilder> {
+		FaceBoxDetectionBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>, max_boxes: u8) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, max_boxes)?;
+
+		let input_image = InputImage::new(&mut feature)?;
+
+		Ok(Self {
+			feature,
+			input_image,
+			bounding_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, filter: TemporalFilter) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, filter.bits())?;
+		Ok(())
+	}
+
+	/// Detects faces in `image`, returning the number of faces found.
+	pub fn run(&mut self, image: &Image) -> Result<usize> {
+		self.input_image.upload(&mut self.feature, image, self.stream.as_deref())?;
+		self.feature.run()?;
+		Ok(self.bounding_boxes.len(&self.feature))
+	}
+}
@@ -1,40 +1,49 @@
-use std::{num::NonZeroUsize, pin::Pin, sync::Arc};
+use std::sync::Arc;
 
-use super::{CUDAStream, FeatureBase, Point2D, Quaternion, Rect, TemporalFilter, Vector3, sys};
-use crate::{
-	Result,
-	cv::{ComponentType, Image, ImageLayout, MemorySpace, PixelFormat}
-};
+use super::{Bound, BoundingBoxes, CUDAStream, FeatureBase, InputImage, Param, Point2D, Quaternion, TemporalFilter, Vector3, sys};
+use crate::{Error, Result, Runtime, Version, cv::Image};
+
+const MIN_SDK_VERSION: Version = Version::new(0, 8, 0);
+const CHEEK_PUFF_SDK_VERSION: Version = Version::new(0, 8, 2);
 
 pub struct FaceExpressionsBuilder {
 	feature: FeatureBase,
 	stream: Option<Arc<CUDAStream>>
 }
 
+impl_builder_common!(FaceExpressionsBuilder);
+
 impl FaceExpressionsBuilder {
-	pub(crate) fn new() -> Result<Self> {
-		let feature = FeatureBase::new("FaceExpressions")?;
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		runtime.require_version(MIN_SDK_VERSION)?;
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_FaceExpressions)?;
 		Ok(FaceExpressionsBuilder { feature, stream: None })
 	}
 
-	pub fn with_cuda_stream(mut self, stream: Arc<CUDAStream>) -> Result<Self> {
-		self.feature.set_config("CUDAStream", &*stream)?;
-		self.stream = Some(stream);
-		Ok(self)
-	}
-
 	pub fn with_temporal(mut self, temporal_filter: TemporalFilter) -> Result<Self> {
-		self.feature.set_config("Temporal", temporal_filter.bits())?;
+		self.feature.set(Param::CONFIG_TEMPORAL, temporal_filter.bits())?;
 		Ok(self)
 	}
 
 	pub fn with_pose(mut self, pose: bool) -> Result<Self> {
-		self.feature.set_config("PoseMode", u32::from(pose))?;
+		self.feature.set(Param::CONFIG_POSE_MODE, u32::from(pose))?;
 		Ok(self)
 	}
 
+	/// Enables the cheek puff expressions, which requires SDK 0.8.2 or newer. Disabling them is a no-op on older SDKs,
+	/// which don't know the parameter.
 	pub fn with_cheek_puff(mut self, enable: bool) -> Result<Self> {
-		self.feature.set_config("EnableCheekPuff", u32::from(enable))?;
+		let found = self.feature.runtime().version()?;
+		if found < CHEEK_PUFF_SDK_VERSION {
+			if !enable {
+				return Ok(self);
+			}
+			return Err(Error::UnsupportedSdkVersion {
+				required: CHEEK_PUFF_SDK_VERSION,
+				found
+			});
+		}
+		self.feature.set(Param::CONFIG_ENABLE_CHEEK_PUFF, u32::from(enable))?;
 		Ok(self)
 	}
 
@@ -46,60 +55,47 @@ impl FaceExpressionsBuilder {
 
 pub struct FaceExpressions {
 	feature: FeatureBase,
-	input_image: Image,
-	pose_rotation: Pin<Box<Quaternion>>,
-	pose_translation: Pin<Box<Vector3>>,
-	expression_coefficients: Vec<f32>,
+	input_image: InputImage,
+	pose_rotation: Bound<Box<Quaternion>>,
+	pose_translation: Bound<Box<Vector3>>,
+	expression_coefficients: Bound<Vec<f32>>,
 	expression_zero_point: Vec<f32>,
 	expression_scale: Vec<f32>,
-	landmarks: Vec<Point2D>,
-	landmark_confidence: Vec<f32>,
-	bounding_boxes: Pin<Box<sys::NvAR_BBoxes>>,
-	bounding_boxes_confidence: Vec<f32>,
-	bounding_boxes_data: Vec<Rect>,
+	landmarks: Bound<Vec<Point2D>>,
+	landmark_confidence: Bound<Vec<f32>>,
+	bounding_boxes: BoundingBoxes,
 	stream: Option<Arc<CUDAStream>>,
 	needs_calibration: bool
 }
 
+impl_feature_common!(FaceExpressions);
+
 impl FaceExpressions {
 	pub fn builder() -> Result<FaceExpressionsBuilder> {
-		FaceExpressionsBuilder::new()
+		FaceExpressionsBuilder::new(Runtime::global()?)
 	}
 
-	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>) -> Result<Self> {
-		let mut output_bbox_data = vec![Rect::default(); 25];
-		let mut output_bboxes = Box::pin(sys::NvAR_BBoxes {
-			boxes: output_bbox_data.as_mut_ptr(),
-			max_boxes: output_bbox_data.len() as u8,
-			num_boxes: 0
-		});
-		feature.set_output("BoundingBoxes", output_bboxes.as_mut())?;
-
-		let mut output_bbox_confidence = vec![0.0; 25];
-		feature.set_output("BoundingBoxesConfidence", &mut output_bbox_confidence[..])?;
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<FaceExpressionsBuilder> {
+		FaceExpressionsBuilder::new(runtime)
+	}
 
-		let landmarks_size = feature.get_config::<u32>("Landmarks_Size")? as usize;
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, 25)?;
 
-		let mut landmarks = vec![Point2D::default(); landmarks_size];
-		feature.set_output("Landmarks", &mut landmarks[..])?;
+		let landmarks_size = feature.get(Param::CONFIG_LANDMARKS_SIZE)? as usize;
 
-		let mut landmark_confidence = vec![0.0f32; landmarks_size];
-		feature.set_output("LandmarksConfidence", &mut landmark_confidence[..])?;
+		let landmarks = feature.bind(Param::OUTPUT_LANDMARKS, vec![Point2D::default(); landmarks_size])?;
+		let landmark_confidence = feature.bind(Param::OUTPUT_LANDMARKS_CONFIDENCE, vec![0.0f32; landmarks_size])?;
 
-		let expr_count = feature.get_config::<u32>("ExpressionCount")? as usize;
-		let mut expression_coefficients = vec![0.0; expr_count];
+		let expr_count = feature.get(Param::CONFIG_EXPRESSION_COUNT)? as usize;
+		let expression_coefficients = feature.bind(Param::OUTPUT_EXPRESSION_COEFFICIENTS, vec![0.0; expr_count])?;
 		let expression_zero_point = vec![0.0; expr_count];
 		let expression_scale = vec![1.0; expr_count];
-		feature.set_output("ExpressionCoefficients", &mut expression_coefficients[..])?;
 
-		let mut pose_rotation: Pin<Box<Quaternion>> = Box::pin(Quaternion::default());
-		feature.set_output("Pose", pose_rotation.as_mut())?;
+		let pose_rotation = feature.bind(Param::OUTPUT_POSE, Box::new(Quaternion::default()))?;
+		let pose_translation = feature.bind(Param::OUTPUT_POSE_TRANSLATION, Box::new(Vector3::default()))?;
 
-		let mut pose_translation = Box::pin(Vector3::default());
-		feature.set_output("PoseTranslation", pose_translation.as_mut())?;
-
-		let mut input_image = Image::new(32, 32, PixelFormat::BGR, ComponentType::U8, ImageLayout::Interleaved, MemorySpace::GPU, NonZeroUsize::new(1))?;
-		feature.set_input("Image", &mut input_image)?;
+		let input_image = InputImage::new(&mut feature)?;
 
 		Ok(Self {
 			feature,
@@ -111,61 +107,46 @@ impl FaceExpressions {
 			expression_zero_point,
 			landmarks,
 			landmark_confidence,
-			bounding_boxes: output_bboxes,
-			bounding_boxes_confidence: output_bbox_confidence,
-			bounding_boxes_data: output_bbox_data,
+			bounding_boxes,
 			stream,
 			needs_calibration: true
 		})
 	}
 
 	pub fn set_temporal(&mut self, filter: TemporalFilter) -> Result<()> {
-		self.feature.set_config("Temporal", filter.bits())?;
+		self.feature.set(Param::CONFIG_TEMPORAL, filter.bits())?;
 		Ok(())
 	}
 
 	pub fn run(&mut self, image: &Image) -> Result<bool> {
-		if self.input_image.width() != image.width() || self.input_image.height() != image.height() {
-			let mut input_image = Image::new(
-				image.width(),
-				image.height(),
-				PixelFormat::BGR,
-				ComponentType::U8,
-				ImageLayout::Interleaved,
-				MemorySpace::GPU,
-				NonZeroUsize::new(1)
-			)?;
-			self.feature.set_input("Image", &mut input_image)?;
-			self.input_image = input_image;
-		}
-
-		if let Some(stream) = &self.stream {
-			image.transfer_to_opt(&mut self.input_image, 1.0, Some(stream), None)?;
-		} else {
-			image.transfer_to(&mut self.input_image)?;
-		}
+		self.input_image.upload(&mut self.feature, image, self.stream.as_deref())?;
 
 		self.feature.run()?;
 
+		let expression_coefficients = self.feature.bound_mut(self.expression_coefficients);
+
 		if self.needs_calibration {
 			self.needs_calibration = false;
 
-			self.expression_zero_point.copy_from_slice(&self.expression_coefficients);
-			for i in 0..self.expression_coefficients.len() {
-				self.expression_scale[i] = 1.0 / (1.0 - self.expression_zero_point[i]);
+			self.expression_zero_point.copy_from_slice(expression_coefficients);
+			for (scale, zero_point) in self.expression_scale.iter_mut().zip(&self.expression_zero_point) {
+				*scale = 1.0 / (1.0 - zero_point);
 			}
 
 			return Ok(false);
 		}
 
-		for i in 0..self.expression_coefficients.len() {
-			let temp = self.expression_coefficients[i];
-			self.expression_coefficients[i] =
-				1.0 - (1.0 - (self.expression_coefficients[i] - self.expression_zero_point[i]).max(0.0) * self.expression_scale[i]);
-			self.expression_coefficients[i] = (1.0 * self.expression_coefficients[i] + 0.0 * temp).clamp(0.0, 1.0);
+		for ((coefficient, zero_point), scale) in expression_coefficients
+			.iter_mut()
+			.zip(&self.expression_zero_point)
+			.zip(&self.expression_scale)
+		{
+			let temp = *coefficient;
+			*coefficient = 1.0 - (1.0 - (*coefficient - zero_point).max(0.0) * scale);
+			*coefficient = (1.0 * *coefficient + 0.0 * temp).clamp(0.0, 1.0);
 		}
 
-		for x in self.expression_coefficients.iter_mut() {
+		for x in expression_coefficients.iter_mut() {
 			*x = x.clamp(0.0, 1.0);
 		}
 
@@ -173,41 +154,26 @@ impl FaceExpressions {
 	}
 
 	pub fn expressions(&self) -> &[f32] {
-		&self.expression_coefficients
+		self.feature.bound(self.expression_coefficients)
 	}
 
 	pub fn rotation(&self) -> &Quaternion {
-		&self.pose_rotation
+		self.feature.bound(self.pose_rotation)
 	}
 
 	pub fn translation(&self) -> &Vector3 {
-		&self.pose_translation
+		self.feature.bound(self.pose_translation)
 	}
 
 	pub fn landmarks(&self) -> &[Point2D] {
-		&self.landmarks
+		self.feature.bound(self.landmarks)
 	}
 
 	pub fn landmark_confidence(&self) -> &[f32] {
-		&self.landmark_confidence
+		self.feature.bound(self.landmark_confidence)
 	}
 
 	pub fn calibrate(&mut self) {
 		self.needs_calibration = true;
 	}
-
-	pub fn bounding_boxes(&self) -> &[Rect] {
-		&self.bounding_boxes_data[..self.bounding_boxes.num_boxes as usize]
-	}
-
-	pub fn bounding_boxes_confidence(&self) -> &[f32] {
-		&self.bounding_boxes_confidence[..self.bounding_boxes.num_boxes as usize]
-	}
-
-	pub fn bounding_boxes_with_confidence(&self) -> Vec<(&Rect, f32)> {
-		self.bounding_boxes_data[..self.bounding_boxes.num_boxes as usize]
-			.iter()
-			.zip(self.bounding_boxes_confidence[..self.bounding_boxes.num_boxes as usize].iter().copied())
-			.collect()
-	}
 }
@@ -0,0 +1,146 @@
+use std::{ffi::CStr, marker::PhantomData, sync::Arc};
+
+use super::{Binding, BindingMut, Bound, CUDAStream, FeatureBase, FeatureDescription, GetNvARValue, ObjectType, Param, ParamType, sys};
+use crate::{Result, Runtime};
+
+/// A handle to an arbitrary AR SDK feature, for features this crate does not wrap.
+///
+/// Buffers can either be borrowed for `'b` with [`Feature::set`], which guarantees they outlive the handle, or handed
+/// over to the feature with [`Feature::bind`] and accessed through the returned [`Bound`] key.
+///
+/// The SDK writes outputs into these buffers without knowing their length, so both methods are `unsafe`: the caller
+/// must size every buffer for the feature's configuration. The typed features in this module, like
+/// [`FaceBoxDetection`](super::FaceBoxDetection), are the safe alternative, since they size & bind their own buffers.
+///
+/// ```no_run
+/// # fn main() -> nvar::Result<()> {
+/// use nvar::ar::{Feature, Param, Point2D, sys};
+///
+/// let mut landmarks = vec![Point2D::default(); 68];
+/// let mut feature = Feature::new(sys::NvAR_Feature_LandmarkDetection)?;
+/// // SAFETY: the default landmark detection model outputs 68 landmarks for a single face.
+/// unsafe { feature.set(Param::OUTPUT_LANDMARKS, &mut landmarks[..]) }?;
+/// feature.load()?;
+/// // ...bind an input image & run
+/// drop(feature);
+/// println!("{:?}", landmarks[0]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Feature<'b> {
+	base: FeatureBase,
+	_bindings: PhantomData<&'b mut ()>
+}
+
+impl<'b> Feature<'b> {
+	/// Creates the feature with the given ID, e.g. [`sys::NvAR_Feature_FaceBoxDetection`], using the
+	/// [global runtime](Runtime::global).
+	pub fn new(feature_id: &CStr) -> Result<Self> {
+		Feature::new_in(Runtime::global()?, feature_id)
+	}
+
+	pub fn new_in(runtime: &Arc<Runtime>, feature_id: &CStr) -> Result<Self> {
+		Ok(Feature {
+			base: FeatureBase::new(runtime, feature_id)?,
+			_bindings: PhantomData
+		})
+	}
+
+	#[inline]
+	pub fn runtime(&self) -> &Arc<Runtime> {
+		self.base.runtime()
+	}
+
+	/// Returns the raw feature handle. The handle is destroyed when the `Feature` is dropped.
+	#[inline]
+	pub fn as_raw(&self) -> sys::NvAR_FeatureHandle {
+		self.base.as_raw()
+	}
+
+	/// Sets a parameter. Buffers are borrowed until the feature is dropped.
+	///
+	/// # Safety
+	/// The caller must guarantee that:
+	/// - every buffer in `value` is at least as long as what the SDK reads from or writes to it for `param` with the
+	///   feature's current configuration, e.g. one [`Point2D`](super::Point2D) per landmark for
+	///   [`Param::OUTPUT_LANDMARKS`](Param::OUTPUT_LANDMARKS);
+	/// - config parameters that determine the size of an output, like
+	///   [`Param::CONFIG_LANDMARKS_SIZE`](Param::CONFIG_LANDMARKS_SIZE), are not changed afterwards unless the outputs
+	///   are rebound with buffers of the new size;
+	/// - the SDK expects a value of type `T` for `param`'s key. The predefined [`Param`] constants are always paired
+	///   with the right type, but keys created with [`Param::new`] are only as correct as their declared type.
+	pub unsafe fn set<T: ParamType + ?Sized>(&mut self, param: Param<T>, value: T::Value<'b>) -> Result<()> {
+		// SAFETY: `value` is borrowed for `'b`, which outlives `self`; the caller guarantees it is large enough.
+		unsafe { self.base.set_unchecked(param, value) }
+	}
+
+	/// Binds `value` to a parameter, transferring ownership of it to the feature. Rebinding the parameter drops the
+	/// previous value only once the SDK no longer points to it.
+	///
+	/// # Safety
+	/// The caller must guarantee that:
+	/// - every buffer in `value` is at least as long as what the SDK reads from or writes to it for `param` with the
+	///   feature's current configuration;
+	/// - config parameters that determine the size of an output are not changed afterwards unless the outputs are
+	///   rebound with buffers of the new size;
+	/// - the SDK expects a value of type `B::Param` for `param`'s key, which the predefined [`Param`] constants
+	///   guarantee but keys created with [`Param::new`] may not.
+	pub unsafe fn bind<B: Binding>(&mut self, param: Param<B::Param>, value: B) -> Result<Bound<B>> {
+		self.base.bind(param, value)
+	}
+
+	/// Returns the contents of a value [bound](Feature::bind) to this feature, or `None` if `key` belongs to another
+	/// feature or its parameter has since been rebound with a value of a different type.
+	pub fn bound<B: Binding>(&self, key: Bound<B>) -> Option<&B::Target> {
+		self.base.try_bound(key)
+	}
+
+	pub fn bound_mut<B: BindingMut>(&mut self, key: Bound<B>) -> Option<&mut B::Target> {
+		self.base.try_bound_mut(key)
+	}
+
+	/// Reads a scalar parameter. Config, input, output & in/out parameters can all be read.
+	pub fn get<T: GetNvARValue>(&self, param: Param<T>) -> Result<T> {
+		self.base.get(param)
+	}
+
+	/// Reads a string parameter, returning `None` if it has not been set.
+	pub fn get_str(&self, param: Param<str>) -> Result<Option<&CStr>> {
+		self.base.get_str(param)
+	}
+
+	pub fn get_f32_array(&self, param: Param<[f32]>) -> Result<&[f32]> {
+		self.base.get_f32_array(param)
+	}
+
+	/// Returns the raw CUDA stream bound to a parameter, which is null if none is bound.
+	pub fn get_cuda_stream(&self, param: Param<CUDAStream>) -> Result<sys::CUstream> {
+		self.base.get_cuda_stream(param)
+	}
+
+	/// Returns a pointer to the object stored in a parameter, e.g. the `NvCVImage` bound to
+	/// [`Param::INPUT_IMAGE`](Param::INPUT_IMAGE), or an SDK-owned output such as
+	/// [`Param::CONFIG_REFERENCE_POSE`](Param::CONFIG_REFERENCE_POSE).
+	///
+	/// The SDK checks that the size of `T::Object` matches the size of the stored object. The pointer is only valid
+	/// while the parameter remains bound.
+	pub fn get_object<T: ObjectType + ?Sized>(&self, param: Param<T>) -> Result<*const T::Object> {
+		self.base.get_object(param)
+	}
+
+	/// Queries the config parameters known for this feature's ID, for debugging. Parameters the feature doesn't support
+	/// are reported as [`ConfigValue::Unsupported`](super::ConfigValue::Unsupported), and string parameters that
+	/// haven't been set as [`ConfigValue::Unset`](super::ConfigValue::Unset).
+	pub fn describe(&self) -> Result<FeatureDescription> {
+		self.base.describe()
+	}
+
+	/// Loads the feature's models. Most config parameters must be set before this is called.
+	pub fn load(&mut self) -> Result<()> {
+		self.base.load()
+	}
+
+	pub fn run(&mut self) -> Result<()> {
+		self.base.run()
+	}
+}
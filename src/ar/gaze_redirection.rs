@@ -0,0 +1,188 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use super::{Bound, BoundingBoxes, CUDAStream, FeatureBase, InputImage, LandmarksSize, Param, Point2D, Point3D, Quaternion, TemporalFilter, Vector3, sys};
+use crate::{Error, Result, Runtime, Version, cv::Image};
+
+const MIN_SDK_VERSION: Version = Version::new(0, 8, 0);
+const EYE_SIZE_SENSITIVITY_RANGE: RangeInclusive<u32> = 2..=6;
+
+pub struct GazeRedirectionBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>,
+	redirect: bool
+}
+
+impl_builder_common!(GazeRedirectionBuilder);
+
+impl GazeRedirectionBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		runtime.require_version(MIN_SDK_VERSION)?;
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_GazeRedirection)?;
+		Ok(GazeRedirectionBuilder {
+			feature,
+			stream: None,
+			redirect: false
+		})
+	}
+
+	pub fn with_temporal(mut self, temporal_filter: TemporalFilter) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, temporal_filter.bits())?;
+		Ok(self)
+	}
+
+	pub fn with_landmarks_size(mut self, size: LandmarksSize) -> Result<Self> {
+		self.feature.set(Param::CONFIG_LANDMARKS_SIZE, size as u32)?;
+		Ok(self)
+	}
+
+	/// Enables writing an [output image](GazeRedirection::output_image) in which the subject's eyes are redirected to
+	/// look at the camera. When disabled, the feature only estimates gaze.
+	pub fn with_gaze_redirect(mut self, enable: bool) -> Result<Self> {
+		self.feature.set(Param::CONFIG_GAZE_REDIRECT, u32::from(enable))?;
+		self.redirect = enable;
+		Ok(self)
+	}
+
+	/// Sets how sensitive redirection is to the size of the eyes, from 2 to 6. Defaults to 3.
+	///
+	/// Returns [`Error::InvalidParameterValue`] if `sensitivity` is out of range.
+	pub fn with_eye_size_sensitivity(mut self, sensitivity: u32) -> Result<Self> {
+		if !EYE_SIZE_SENSITIVITY_RANGE.contains(&sensitivity) {
+			return Err(Error::InvalidParameterValue {
+				parameter: Param::CONFIG_EYE_SIZE_SENSITIVITY.name(),
+				reason: "eye size sensitivity must be between 2 and 6"
+			});
+		}
+		self.feature.set(Param::CONFIG_EYE_SIZE_SENSITIVITY, sensitivity)?;
+		Ok(self)
+	}
+
+	pub fn load(mut self) -> Result<GazeRedirection> {
+		self.feature.load()?;
+		GazeRedirection::new(self.feature, self.stream, self.redirect)
+	}
+}
+
+pub struct GazeRedirection {
+	feature: FeatureBase,
+	input_image: InputImage,
+	output_image: Option<Bound<Image>>,
+	gaze_angles: Bound<Vec<f32>>,
+	gaze_direction: Bound<Vec<Point3D>>,
+	head_translation: Bound<Vec<f32>>,
+	head_pose: Bound<Box<Quaternion>>,
+	landmarks: Bound<Vec<Point2D>>,
+	landmark_confidence: Bound<Vec<f32>>,
+	bounding_boxes: BoundingBoxes,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(GazeRedirection);
+
+impl GazeRedirection {
+	pub fn builder() -> Result<GazeRedirectionBuilder> {
+		GazeRedirectionBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<GazeRedirectionBuilder> {
+		GazeRedirectionBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>, redirect: bool) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, 25)?;
+
+		let landmarks_size = feature.get(Param::CONFIG_LANDMARKS_SIZE)? as usize;
+
+		let landmarks = feature.bind(Param::OUTPUT_LANDMARKS, vec![Point2D::default(); landmarks_size])?;
+		let landmark_confidence = feature.bind(Param::OUTPUT_LANDMARKS_CONFIDENCE, vec![0.0f32; landmarks_size])?;
+
+		let gaze_angles = feature.bind(Param::OUTPUT_GAZE_VECTOR, vec![0.0f32; 2])?;
+		let gaze_direction = feature.bind(Param::OUTPUT_GAZE_DIRECTION, vec![Point3D::default(); 2])?;
+		let head_translation = feature.bind(Param::OUTPUT_HEAD_TRANSLATION, vec![0.0f32; 3])?;
+		let head_pose = feature.bind(Param::OUTPUT_HEAD_POSE, Box::new(Quaternion::default()))?;
+
+		let input_image = InputImage::new(&mut feature)?;
+		feature.set(Param::INPUT_WIDTH, 32u32)?;
+		feature.set(Param::INPUT_HEIGHT, 32u32)?;
+
+		let output_image = if redirect {
+			let output_image = InputImage::alloc(feature.runtime(), 32, 32)?;
+			Some(feature.bind(Param::OUTPUT_IMAGE, output_image)?)
+		} else {
+			None
+		};
+
+		Ok(Self {
+			feature,
+			input_image,
+			output_image,
+			gaze_angles,
+			gaze_direction,
+			head_translation,
+			head_pose,
+			landmarks,
+			landmark_confidence,
+			bounding_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, filter: TemporalFilter) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, filter.bits())?;
+		Ok(())
+	}
+
+	/// Estimates the gaze of the face in `image`, and writes the redirected image if gaze redirection is enabled.
+	pub fn run(&mut self, image: &Image) -> Result<()> {
+		if self.input_image.upload(&mut self.feature, image, self.stream.as_deref())? {
+			self.feature.set(Param::INPUT_WIDTH, image.width())?;
+			self.feature.set(Param::INPUT_HEIGHT, image.height())?;
+			if self.output_image.is_some() {
+				let output_image = InputImage::alloc(self.feature.runtime(), image.width(), image.height())?;
+				self.output_image = Some(self.feature.bind(Param::OUTPUT_IMAGE, output_image)?);
+			}
+		}
+		self.feature.run()?;
+		Ok(())
+	}
+
+	/// The GPU-resident BGR image with the subject's gaze redirected, if the feature was built
+	/// [with gaze redirection](GazeRedirectionBuilder::with_gaze_redirect).
+	pub fn output_image(&self) -> Option<&Image> {
+		self.output_image.map(|output_image| self.feature.bound(output_image))
+	}
+
+	/// The estimated gaze as `(pitch, yaw)` angles in radians.
+	pub fn gaze_angles(&self) -> (f32, f32) {
+		let gaze_angles = self.feature.bound(self.gaze_angles);
+		(gaze_angles[0], gaze_angles[1])
+	}
+
+	/// The estimated gaze as a pair of 3D points: the origin of the gaze between the eyes, and a point along its
+	/// direction.
+	pub fn gaze_direction(&self) -> &[Point3D] {
+		self.feature.bound(self.gaze_direction)
+	}
+
+	pub fn head_translation(&self) -> Vector3 {
+		let head_translation = self.feature.bound(self.head_translation);
+		Vector3 {
+			x: head_translation[0],
+			y: head_translation[1],
+			z: head_translation[2]
+		}
+	}
+
+	pub fn head_pose(&self) -> &Quaternion {
+		self.feature.bound(self.head_pose)
+	}
+
+	/// The facial landmarks used to locate the eyes, including the eye contours.
+	pub fn landmarks(&self) -> &[Point2D] {
+		self.feature.bound(self.landmarks)
+	}
+
+	pub fn landmark_confidence(&self) -> &[f32] {
+		self.feature.bound(self.landmark_confidence)
+	}
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use super::{Bound, BoundingBoxBuffer, BoundingBoxes, CUDAStream, FeatureBase, InputImage, Param, Point2D, Quaternion, Rect, TemporalFilter, sys};
+use crate::{Error, Result, Runtime, cv::Image};
+
+/// The number of facial landmarks detected by [`LandmarkDetection`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LandmarksSize {
+	#[default]
+	Points68 = 68,
+	Points126 = 126
+}
+
+pub struct LandmarkDetectionBuilder {
+	feature: FeatureBase,
+	stream: Option<Arc<CUDAStream>>,
+	input_box: bool
+}
+
+impl_builder_common!(LandmarkDetectionBuilder);
+
+impl LandmarkDetectionBuilder {
+	pub(crate) fn new(runtime: &Arc<Runtime>) -> Result<Self> {
+		let feature = FeatureBase::new(runtime, sys::NvAR_Feature_LandmarkDetection)?;
+		Ok(LandmarkDetectionBuilder {
+			feature,
+			stream: None,
+			input_box: false
+		})
+	}
+
+	pub fn with_temporal(mut self, temporal_filter: TemporalFilter) -> Result<Self> {
+		self.feature.set(Param::CONFIG_TEMPORAL, temporal_filter.bits())?;
+		Ok(self)
+	}
+
+	pub fn with_landmarks_size(mut self, size: LandmarksSize) -> Result<Self> {
+		self.feature.set(Param::CONFIG_LANDMARKS_SIZE, size as u32)?;
+		Ok(self)
+	}
+
+	/// Uses the face box supplied to [`LandmarkDetection::run_with_box`] instead of detecting faces internally, so that
+	/// landmark detection can be chained after another face detector.
+	pub fn with_input_box(mut self, enable: bool) -> Self {
+		self.input_box = enable;
+		self
+	}
+
+	pub fn load(mut self) -> Result<LandmarkDetection> {
+		self.feature.load()?;
+		LandmarkDetection::new(self.feature, self.stream, self.input_box)
+	}
+}
+
+pub struct LandmarkDetection {
+	feature: FeatureBase,
+	input_image: InputImage,
+	input_box: Option<Bound<BoundingBoxBuffer>>,
+	pose_rotation: Bound<Box<Quaternion>>,
+	landmarks: Bound<Vec<Point2D>>,
+	landmark_confidence: Bound<Vec<f32>>,
+	bounding_boxes: BoundingBoxes,
+	stream: Option<Arc<CUDAStream>>
+}
+
+impl_feature_common!(LandmarkDetection);
+
+impl LandmarkDetection {
+	pub fn builder() -> Result<LandmarkDetectionBuilder> {
+		LandmarkDetectionBuilder::new(Runtime::global()?)
+	}
+
+	pub fn builder_in(runtime: &Arc<Runtime>) -> Result<LandmarkDetectionBuilder> {
+		LandmarkDetectionBuilder::new(runtime)
+	}
+
+	pub(crate) fn new(mut feature: FeatureBase, stream: Option<Arc<CUDAStream>>, input_box: bool) -> Result<Self> {
+		let bounding_boxes = BoundingBoxes::bind_output(&mut feature, 25)?;
+
+		let landmarks_size = feature.get(Param::CONFIG_LANDMARKS_SIZE)? as usize;
+
+		let landmarks = feature.bind(Param::OUTPUT_LANDMARKS, vec![Point2D::default(); landmarks_size])?;
+		let landmark_confidence = feature.bind(Param::OUTPUT_LANDMARKS_CONFIDENCE, vec![0.0f32; landmarks_size])?;
+		let pose_rotation = feature.bind(Param::OUTPUT_POSE, Box::new(Quaternion::default()))?;
+
+		let input_box = if input_box {
+			Some(feature.bind(Param::INPUT_BOUNDING_BOXES, BoundingBoxBuffer::from_boxes(vec![Rect::default()]))?)
+		} else {
+			None
+		};
+
+		let input_image = InputImage::new(&mut feature)?;
+
+		Ok(Self {
+			feature,
+			input_image,
+			input_box,
+			pose_rotation,
+			landmarks,
+			landmark_confidence,
+			bounding_boxes,
+			stream
+		})
+	}
+
+	pub fn set_temporal(&mut self, filter: TemporalFilter) -> Result<()> {
+		self.feature.set(Param::CONFIG_TEMPORAL, filter.bits())?;
+		Ok(())
+	}
+
+	/// Detects a face in `image` and its landmarks.
+	///
+	/// Returns [`Error::FeatureMisconfigured`] if the feature was built
+	/// [with an input box](LandmarkDetectionBuilder::with_input_box); use [`LandmarkDetection::run_with_box`] instead.
+	pub fn run(&mut self, image: &Image) -> Result<()> {
+		if self.input_box.is_some() {
+			return Err(Error::FeatureMisconfigured("a feature built with an input box must be run with `run_with_box`"));
+		}
+		self.run_inner(image)
+	}
+
+	/// Detects the landmarks of the face in `face` within `image`.
+	///
+	/// Returns [`Error::FeatureMisconfigured`] if the feature was not built
+	/// [with an input box](LandmarkDetectionBuilder::with_input_box).
+	pub fn run_with_box(&mut self, image: &Image, face: &Rect) -> Result<()> {
+		let Some(input_box) = self.input_box else {
+			return Err(Error::FeatureMisconfigured("`run_with_box` requires a feature built with an input box"));
+		};
+		self.feature.bound_mut(input_box)[0] = face.clone();
+		self.run_inner(image)
+	}
+
+	fn run_inner(&mut self, image: &Image) -> Result<()> {
+		self.input_image.upload(&mut self.feature, image, self.stream.as_deref())?;
+		self.feature.run()?;
+		Ok(())
+	}
+
+	pub fn rotation(&self) -> &Quaternion {
+		self.feature.bound(self.pose_rotation)
+	}
+
+	pub fn landmarks(&self) -> &[Point2D] {
+		self.feature.bound(self.landmarks)
+	}
+
+	pub fn landmark_confidence(&self) -> &[f32] {
+		self.feature.bound(self.landmark_confidence)
+	}
+}
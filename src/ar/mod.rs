@@ -1,209 +1,495 @@
+//! Safe bindings to the features of the AR SDK.
+//!
+//! Each wrapper, like [`FaceBoxDetection`] or [`LandmarkDetection`], sizes the buffers it binds from the feature's own
+//! configuration and owns them for as long as the SDK points to them, so it can be used entirely from safe code.
+//! [`Feature`] can drive any feature ID, but because the SDK writes outputs without knowing their length, binding
+//! buffers through it is `unsafe`; prefer a typed wrapper wherever one exists.
+
 use std::{
 	ffi::{CStr, CString},
-	pin::Pin
+	num::NonZeroUsize,
+	path::Path,
+	pin::Pin,
+	sync::Arc
 };
 
-use crate::{Result, cv::Image};
+use crate::{
+	Error, Result, Runtime,
+	cv::{ComponentType, Image, ImageLayout, MemorySpace, PixelFormat}
+};
 
 pub mod sys;
 
 pub use self::sys::{
-	NvAR_FaceMesh as FaceMesh, NvAR_Frustum as Frustum, NvAR_Point2f as Point2D, NvAR_Point3f as Point3D, NvAR_Quaternion as Quaternion, NvAR_Rect as Rect,
+	NvAR_Frustum as Frustum, NvAR_Point2f as Point2D, NvAR_Point3f as Point3D, NvAR_Quaternion as Quaternion, NvAR_Rect as Rect,
 	NvAR_RenderingParams as RenderingParams, NvAR_TemporalFilter as TemporalFilter, NvAR_TrackingBBox as TrackingBoundingBox, NvAR_Vector2f as Vector2,
 	NvAR_Vector3f as Vector3, NvAR_Vector3u16 as Vector3U16
 };
 
+/// Implements the config methods shared by every feature builder, which must have `feature: FeatureBase` &
+/// `stream: Option<Arc<CUDAStream>>` fields.
+macro_rules! impl_builder_common {
+	($builder:ty) => {
+		impl $builder {
+			pub fn with_cuda_stream(mut self, stream: ::std::sync::Arc<$crate::ar::CUDAStream>) -> $crate::Result<Self> {
+				self.feature
+					.bind($crate::ar::Param::CONFIG_CUDA_STREAM, ::std::sync::Arc::clone(&stream))?;
+				self.stream = Some(stream);
+				Ok(self)
+			}
+
+			/// Loads models from `path` instead of the SDK's model directory. Returns an error if `path` is not an
+			/// existing directory.
+			pub fn with_model_dir(mut self, path: impl AsRef<::std::path::Path>) -> $crate::Result<Self> {
+				self.feature.set_model_dir(path.as_ref())?;
+				Ok(self)
+			}
+
+			pub fn with_mode(mut self, mode: $crate::ar::Mode) -> $crate::Result<Self> {
+				self.feature.set($crate::ar::Param::CONFIG_MODE, mode as u32)?;
+				Ok(self)
+			}
+
+			/// Enables CUDA graph optimization, which reduces launch overhead on every run.
+			pub fn with_cuda_graph(mut self, enable: bool) -> $crate::Result<Self> {
+				self.feature.set($crate::ar::Param::CONFIG_USE_CUDA_GRAPH, u32::from(enable))?;
+				Ok(self)
+			}
+		}
+	};
+}
+
+/// Implements the accessors shared by every feature, which must have `feature: FeatureBase` &
+/// `bounding_boxes: BoundingBoxes` fields.
+macro_rules! impl_feature_common {
+	($feature:ty) => {
+		impl $feature {
+			/// Reports the feature's current config. See [`Feature::describe`]($crate::ar::Feature::describe).
+			pub fn describe(&self) -> $crate::Result<$crate::ar::FeatureDescription> {
+				self.feature.describe()
+			}
+
+			pub fn bounding_boxes(&self) -> &[$crate::ar::Rect] {
+				self.bounding_boxes.boxes(&self.feature)
+			}
+
+			pub fn bounding_boxes_confidence(&self) -> &[f32] {
+				self.bounding_boxes.confidence(&self.feature)
+			}
+
+			pub fn bounding_boxes_with_confidence(&self) -> Vec<(&$crate::ar::Rect, f32)> {
+				self.bounding_boxes.with_confidence(&self.feature)
+			}
+		}
+	};
+}
+
+mod binding;
+mod body_detection;
+mod body_pose_estimation;
+mod describe;
+mod face_3d_reconstruction;
+mod face_box_detection;
 mod face_expressions;
-pub use self::face_expressions::{FaceExpressions, FaceExpressionsBuilder};
+mod feature;
+mod gaze_redirection;
+mod landmark_detection;
+mod param;
+use self::binding::Bindings;
+pub use self::{
+	binding::{Binding, BindingMut, Bound, BoundingBoxBuffer, FaceMeshBuffer, TrackingBoundingBoxBuffer},
+	body_detection::{BodyDetection, BodyDetectionBuilder},
+	body_pose_estimation::{BodyJoint, BodyPoseEstimation, BodyPoseEstimationBuilder, TrackedPerson},
+	describe::{ConfigValue, FeatureDescription},
+	face_3d_reconstruction::{Face3DReconstruction, Face3DReconstructionBuilder, FaceMesh},
+	face_box_detection::{FaceBoxDetection, FaceBoxDetectionBuilder},
+	face_expressions::{FaceExpressions, FaceExpressionsBuilder},
+	feature::Feature,
+	gaze_redirection::{GazeRedirection, GazeRedirectionBuilder},
+	landmark_detection::{LandmarkDetection, LandmarkDetectionBuilder, LandmarksSize},
+	param::{ObjectType, Param, ParamType}
+};
+
+/// Selects which of a feature's models to run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+	/// The higher quality, slower model.
+	#[default]
+	Quality = 0,
+	/// The faster, lower quality model.
+	Performance = 1
+}
 
-pub struct CUDAStream(pub(crate) sys::CUstream);
+pub struct CUDAStream {
+	stream: sys::CUstream,
+	runtime: Arc<Runtime>
+}
 
 unsafe impl Send for CUDAStream {}
 unsafe impl Sync for CUDAStream {}
 
 impl CUDAStream {
 	pub fn new() -> Result<Self> {
-		Ok(CUDAStream(unsafe { sys::NvAR_CudaStreamCreate(crate::nvar_lib_handle())? }))
+		CUDAStream::new_in(Runtime::global()?)
+	}
+
+	pub fn new_in(runtime: &Arc<Runtime>) -> Result<Self> {
+		let stream = unsafe { sys::NvAR_CudaStreamCreate(runtime.nvar_api())? };
+		Ok(CUDAStream { stream, runtime: Arc::clone(runtime) })
+	}
+
+	#[inline]
+	pub fn as_raw(&self) -> sys::CUstream {
+		self.stream
+	}
+
+	#[inline]
+	pub fn runtime(&self) -> &Arc<Runtime> {
+		&self.runtime
 	}
 }
 
 impl Drop for CUDAStream {
 	fn drop(&mut self) {
-		unsafe { sys::NvAR_CudaStreamDestroy(crate::nvar_lib_handle(), self.0) }.unwrap();
+		unsafe { sys::NvAR_CudaStreamDestroy(self.runtime.nvar_api(), self.stream) }.unwrap();
 	}
 }
 
-pub(crate) struct FeatureBase(sys::NvAR_FeatureHandle);
+pub(crate) struct FeatureBase {
+	handle: sys::NvAR_FeatureHandle,
+	runtime: Arc<Runtime>,
+	id: CString,
+	bindings: Bindings
+}
 
 impl FeatureBase {
-	pub fn new(feature_name: &'static str) -> Result<FeatureBase> {
-		let feature_name = CString::new(feature_name).unwrap();
-		let ptr = unsafe { sys::NvAR_Create(crate::nvar_lib_handle(), feature_name.as_ptr()) }?;
-		Ok(FeatureBase(ptr))
+	pub fn new(runtime: &Arc<Runtime>, feature_id: &CStr) -> Result<FeatureBase> {
+		let handle = unsafe { sys::NvAR_Create(runtime.nvar_api(), feature_id.as_ptr()) }?;
+		Ok(FeatureBase {
+			handle,
+			runtime: Arc::clone(runtime),
+			id: feature_id.to_owned(),
+			bindings: Bindings::new()
+		})
 	}
 
-	pub fn set_config<'s, T: SetNvARValue + 's>(&'s mut self, option: &'static str, value: T) -> Result<()> {
-		let option = CString::new(format!("NvAR_Parameter_Config_{option}")).unwrap();
-		value.set(option.as_ptr(), self.0)
+	#[inline]
+	pub fn runtime(&self) -> &Arc<Runtime> {
+		&self.runtime
 	}
-	pub fn set_input<'s, T: SetNvARValue + 's>(&'s mut self, option: &'static str, value: T) -> Result<()> {
-		let option = CString::new(format!("NvAR_Parameter_Input_{option}")).unwrap();
-		value.set(option.as_ptr(), self.0)
+
+	#[inline]
+	pub fn as_raw(&self) -> sys::NvAR_FeatureHandle {
+		self.handle
+	}
+
+	#[inline]
+	pub fn id(&self) -> &CStr {
+		&self.id
+	}
+
+	pub fn describe(&self) -> Result<FeatureDescription> {
+		describe::describe(self)
+	}
+
+	/// Points the feature at the models in `path`, which must be an existing directory.
+	pub fn set_model_dir(&mut self, path: &Path) -> Result<()> {
+		let model_dir = match path.to_str() {
+			Some(model_dir) if path.is_dir() => CString::new(model_dir)?,
+			_ => return Err(Error::InvalidModelDir(path.to_path_buf()))
+		};
+		self.bind(Param::CONFIG_MODEL_DIR, model_dir)?;
+		Ok(())
+	}
+
+	/// Sets a parameter to a value that does not borrow anything. Buffers must be [bound](FeatureBase::bind) instead.
+	pub fn set<T: ParamType + ?Sized>(&mut self, param: Param<T>, value: T::Value<'static>) -> Result<()> {
+		unsafe { self.set_unchecked(param, value) }
+	}
+
+	/// # Safety
+	/// Anything borrowed by `value` must remain valid until the parameter is rebound or the feature is dropped.
+	pub unsafe fn set_unchecked<T: ParamType + ?Sized>(&mut self, param: Param<T>, value: T::Value<'_>) -> Result<()> {
+		unsafe { SetNvARValue::set(value, &self.runtime, self.handle, param.name()) }
+	}
+
+	/// Binds `value` to a parameter, taking ownership of it. Any value previously bound to the parameter is dropped
+	/// only after the SDK has been pointed at the new one.
+	pub fn bind<B: Binding>(&mut self, param: Param<B::Param>, mut value: B) -> Result<Bound<B>> {
+		unsafe { SetNvARValue::set(value.value(), &self.runtime, self.handle, param.name()) }?;
+		Ok(self.bindings.insert(param, value))
 	}
-	pub fn set_output<'s, T: SetNvARValue + 's>(&'s mut self, option: &'static str, value: T) -> Result<()> {
-		let option = CString::new(format!("NvAR_Parameter_Output_{option}")).unwrap();
-		value.set(option.as_ptr(), self.0)
+
+	pub fn try_bound<B: Binding>(&self, key: Bound<B>) -> Option<&B::Target> {
+		self.bindings.get(key).map(Binding::target)
+	}
+
+	pub fn try_bound_mut<B: BindingMut>(&mut self, key: Bound<B>) -> Option<&mut B::Target> {
+		self.bindings.get_mut(key).map(BindingMut::target_mut)
 	}
-	pub fn set_in_out<'s, T: SetNvARValue + 's>(&'s mut self, option: &'static str, value: T) -> Result<()> {
-		let option = CString::new(format!("NvAR_Parameter_InOut_{option}")).unwrap();
-		value.set(option.as_ptr(), self.0)
+
+	/// # Panics
+	/// Panics if `key` was not returned by this feature's [`FeatureBase::bind`].
+	pub fn bound<B: Binding>(&self, key: Bound<B>) -> &B::Target {
+		self.try_bound(key).expect("binding does not belong to this feature")
+	}
+
+	/// # Panics
+	/// Panics if `key` was not returned by this feature's [`FeatureBase::bind`].
+	pub fn bound_mut<B: BindingMut>(&mut self, key: Bound<B>) -> &mut B::Target {
+		self.try_bound_mut(key).expect("binding does not belong to this feature")
+	}
+
+	/// Returns the bound value itself. Callers must not replace it, as the SDK holds a pointer into it.
+	fn binding_mut<B: Binding>(&mut self, key: Bound<B>) -> &mut B {
+		self.bindings.get_mut(key).expect("binding does not belong to this feature")
 	}
 
-	pub fn get_config<'s, T: GetNvARValue + 's>(&'s self, option: &'static str) -> Result<T> {
-		let option = CString::new(format!("NvAR_Parameter_Config_{option}")).unwrap();
-		T::get(option.as_ptr(), self.0)
+	pub fn get<T: GetNvARValue>(&self, param: Param<T>) -> Result<T> {
+		unsafe { T::get(&self.runtime, self.handle, param.name()) }
+	}
+
+	pub fn get_str(&self, param: Param<str>) -> Result<Option<&CStr>> {
+		unsafe { <Option<&CStr>>::get(&self.runtime, self.handle, param.name()) }
+	}
+
+	pub fn get_f32_array(&self, param: Param<[f32]>) -> Result<&[f32]> {
+		unsafe { <&[f32]>::get(&self.runtime, self.handle, param.name()) }
+	}
+
+	pub fn get_cuda_stream(&self, param: Param<CUDAStream>) -> Result<sys::CUstream> {
+		unsafe { sys::CUstream::get(&self.runtime, self.handle, param.name()) }
+	}
+
+	pub fn get_object<T: ObjectType + ?Sized>(&self, param: Param<T>) -> Result<*const T::Object> {
+		unsafe { <*const T::Object>::get(&self.runtime, self.handle, param.name()) }
 	}
 
 	pub fn load(&mut self) -> Result<()> {
-		unsafe { sys::NvAR_Load(crate::nvar_lib_handle(), self.0) }?;
+		unsafe { sys::NvAR_Load(self.runtime.nvar_api(), self.handle) }?;
 		Ok(())
 	}
 
 	pub fn run(&self) -> Result<()> {
-		unsafe { sys::NvAR_Run(crate::nvar_lib_handle(), self.0) }?;
+		unsafe { sys::NvAR_Run(self.runtime.nvar_api(), self.handle) }?;
 		Ok(())
 	}
 }
 
 impl Drop for FeatureBase {
 	fn drop(&mut self) {
-		unsafe { sys::NvAR_Destroy(crate::nvar_lib_handle(), self.0) }.unwrap();
+		unsafe { sys::NvAR_Destroy(self.runtime.nvar_api(), self.handle) }.unwrap();
+	}
+}
+
+/// A GPU-resident BGR image bound as a feature's `Image` input. Source images are transferred into it before each run,
+/// and it is reallocated & rebound whenever the source resolution changes.
+pub(crate) struct InputImage(Bound<Image>);
+
+impl InputImage {
+	pub fn new(feature: &mut FeatureBase) -> Result<Self> {
+		let image = InputImage::alloc(feature.runtime(), 32, 32)?;
+		Ok(InputImage(feature.bind(Param::INPUT_IMAGE, image)?))
+	}
+
+	fn alloc(runtime: &Arc<Runtime>, width: u32, height: u32) -> Result<Image> {
+		Image::new_in(runtime, width, height, PixelFormat::BGR, ComponentType::U8, ImageLayout::Interleaved, MemorySpace::GPU, NonZeroUsize::new(1))
+	}
+
+	/// Returns `true` if the image had to be reallocated.
+	pub fn upload(&mut self, feature: &mut FeatureBase, image: &Image, stream: Option<&CUDAStream>) -> Result<bool> {
+		let current = feature.bound(self.0);
+		let resized = current.width() != image.width() || current.height() != image.height();
+		if resized {
+			let input_image = InputImage::alloc(feature.runtime(), image.width(), image.height())?;
+			self.0 = feature.bind(Param::INPUT_IMAGE, input_image)?;
+		}
+
+		let input_image = feature.binding_mut(self.0);
+		if let Some(stream) = stream {
+			image.transfer_to_opt(input_image, 1.0, Some(stream), None)?;
+		} else {
+			image.transfer_to(input_image)?;
+		}
+		Ok(resized)
+	}
+}
+
+/// Output buffers for the `BoundingBoxes` & `BoundingBoxesConfidence` outputs shared by most features.
+pub(crate) struct BoundingBoxes {
+	boxes: Bound<BoundingBoxBuffer>,
+	confidence: Bound<Vec<f32>>
+}
+
+impl BoundingBoxes {
+	pub fn bind_output(feature: &mut FeatureBase, max_boxes: u8) -> Result<Self> {
+		Ok(BoundingBoxes {
+			boxes: feature.bind(Param::OUTPUT_BOUNDING_BOXES, BoundingBoxBuffer::new(max_boxes))?,
+			confidence: feature.bind(Param::OUTPUT_BOUNDING_BOXES_CONFIDENCE, vec![0.0; max_boxes as usize])?
+		})
+	}
+
+	#[inline]
+	pub fn len(&self, feature: &FeatureBase) -> usize {
+		feature.bound(self.boxes).len()
+	}
+
+	#[inline]
+	pub fn boxes<'f>(&self, feature: &'f FeatureBase) -> &'f [Rect] {
+		feature.bound(self.boxes)
+	}
+
+	#[inline]
+	pub fn confidence<'f>(&self, feature: &'f FeatureBase) -> &'f [f32] {
+		&feature.bound(self.confidence)[..self.len(feature)]
+	}
+
+	pub fn with_confidence<'f>(&self, feature: &'f FeatureBase) -> Vec<(&'f Rect, f32)> {
+		self.boxes(feature).iter().zip(self.confidence(feature).iter().copied()).collect()
 	}
 }
 
 pub trait GetNvARValue: Sized {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self>;
+	/// # Safety
+	/// `feature` must be a valid feature handle created by `runtime`.
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self>;
 }
 impl GetNvARValue for u32 {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetU32(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetU32(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
 impl GetNvARValue for i32 {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetS32(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetS32(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
 impl GetNvARValue for f32 {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetF32(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetF32(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
 impl GetNvARValue for f64 {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetF64(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetF64(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
 impl GetNvARValue for u64 {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetU64(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetU64(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
-impl GetNvARValue for &CStr {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		let ptr = unsafe { sys::NvAR_GetString(crate::nvar_lib_handle(), feature, name) }?;
-		Ok(unsafe { CStr::from_ptr(ptr) })
+impl GetNvARValue for Option<&CStr> {
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		let ptr = unsafe { sys::NvAR_GetString(runtime.nvar_api(), feature, name.as_ptr()) }?;
+		// The SDK reports string parameters that were never set as null.
+		Ok((!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }))
 	}
 }
 impl GetNvARValue for &[f32] {
-	fn get(name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<Self> {
-		unsafe { sys::NvAR_GetF32Array(crate::nvar_lib_handle(), feature, name) }
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetF32Array(runtime.nvar_api(), feature, name.as_ptr()) }
+	}
+}
+impl GetNvARValue for sys::CUstream {
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetCudaStream(runtime.nvar_api(), feature, name.as_ptr()) }
+	}
+}
+impl<T> GetNvARValue for *const T {
+	unsafe fn get(runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<Self> {
+		unsafe { sys::NvAR_GetObject::<T>(runtime.nvar_api(), feature, name.as_ptr()) }
 	}
 }
 
 pub trait SetNvARValue {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()>;
+	/// # Safety
+	/// `feature` must be a valid feature handle created by `runtime`.
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()>;
 }
 
 impl SetNvARValue for CUDAStream {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetCudaStream(crate::nvar_lib_handle(), feature, name, self.0) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetCudaStream(runtime.nvar_api(), feature, name.as_ptr(), self.as_raw()) }
 	}
 }
 impl SetNvARValue for u32 {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetU32(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetU32(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for i32 {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetS32(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetS32(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for f32 {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetF32(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetF32(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for f64 {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetF64(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetF64(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for u64 {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetU64(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetU64(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for &mut Vec<f32> {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetF32Array(crate::nvar_lib_handle(), feature, name, self) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetF32Array(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
 impl SetNvARValue for &mut [f32] {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetF32Array(crate::nvar_lib_handle(), feature, name, self) }
-	}
-}
-impl SetNvARValue for &str {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		let s = CString::new(self).unwrap();
-		unsafe { sys::NvAR_SetString(crate::nvar_lib_handle(), feature, name, s.as_ptr()) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetF32Array(runtime.nvar_api(), feature, name.as_ptr(), self) }
 	}
 }
-impl SetNvARValue for String {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		let s = CString::new(self).unwrap();
-		unsafe { sys::NvAR_SetString(crate::nvar_lib_handle(), feature, name, s.as_ptr()) }
+impl SetNvARValue for &CStr {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetString(runtime.nvar_api(), feature, name.as_ptr(), self.as_ptr()) }
 	}
 }
 impl SetNvARValue for &CUDAStream {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetCudaStream(crate::nvar_lib_handle(), feature, name, self.0) }
-	}
-}
-impl SetNvARValue for Pin<&mut sys::NvAR_BBoxes> {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetObject(crate::nvar_lib_handle(), feature, name, self.get_mut() as *mut _) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetCudaStream(runtime.nvar_api(), feature, name.as_ptr(), self.as_raw()) }
 	}
 }
 impl SetNvARValue for Pin<&mut Quaternion> {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetObject(crate::nvar_lib_handle(), feature, name, self.get_mut() as *mut _) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.get_mut() as *mut _) }
 	}
 }
 impl SetNvARValue for Pin<&mut Vector3> {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetObject(crate::nvar_lib_handle(), feature, name, self.get_mut() as *mut _) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.get_mut() as *mut _) }
+	}
+}
+impl SetNvARValue for Pin<&mut RenderingParams> {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.get_mut() as *mut _) }
 	}
 }
 impl SetNvARValue for &mut Image {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetObject(crate::nvar_lib_handle(), feature, name, self.as_ptr()) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.as_ptr()) }
 	}
 }
 impl SetNvARValue for &mut [Point2D] {
-	fn set(self, name: *const i8, feature: sys::NvAR_FeatureHandle) -> Result<()> {
-		unsafe { sys::NvAR_SetObject(crate::nvar_lib_handle(), feature, name, self.as_mut_ptr()) }
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.as_mut_ptr()) }
+	}
+}
+impl SetNvARValue for &mut [Point3D] {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.as_mut_ptr()) }
+	}
+}
+impl SetNvARValue for &mut [Quaternion] {
+	unsafe fn set(self, runtime: &Runtime, feature: sys::NvAR_FeatureHandle, name: &CStr) -> Result<()> {
+		unsafe { sys::NvAR_SetObject(runtime.nvar_api(), feature, name.as_ptr(), self.as_mut_ptr()) }
 	}
 }
@@ -0,0 +1,250 @@
+use std::{ffi::CStr, fmt, marker::PhantomData, pin::Pin};
+
+use super::{
+	BoundingBoxBuffer, CUDAStream, FaceMeshBuffer, Point2D, Point3D, Quaternion, RenderingParams, SetNvARValue, TrackingBoundingBoxBuffer, Vector3, sys
+};
+use crate::cv::Image;
+
+/// Maps the type of a [`Param`] to the value passed when setting it.
+///
+/// Parameters holding SDK structs, like [`Param::OUTPUT_BOUNDING_BOXES`](Param::OUTPUT_BOUNDING_BOXES), take the
+/// owning buffer types from this module rather than the raw structs, so the struct's pointers & capacity always
+/// describe a live allocation.
+pub trait ParamType {
+	type Value<'a>: SetNvARValue;
+}
+
+macro_rules! impl_param_type {
+	($($ty:ty => $value:ty;)*) => {
+		$(impl ParamType for $ty {
+			type Value<'a> = $value;
+		})*
+	};
+}
+
+impl_param_type! {
+	u32 => u32;
+	i32 => i32;
+	f32 => f32;
+	f64 => f64;
+	u64 => u64;
+	str => &'a CStr;
+	CUDAStream => &'a CUDAStream;
+	Image => &'a mut Image;
+	[f32] => &'a mut [f32];
+	[Point2D] => &'a mut [Point2D];
+	[Point3D] => &'a mut [Point3D];
+	[Quaternion] => &'a mut [Quaternion];
+	Quaternion => Pin<&'a mut Quaternion>;
+	Vector3 => Pin<&'a mut Vector3>;
+	RenderingParams => Pin<&'a mut RenderingParams>;
+	sys::NvAR_BBoxes => &'a mut BoundingBoxBuffer;
+	sys::NvAR_TrackingBBoxes => &'a mut TrackingBoundingBoxBuffer;
+	sys::NvAR_FaceMesh => &'a mut FaceMeshBuffer;
+}
+
+/// A [`ParamType`] that is stored in the SDK as a pointer to an object, which can be read back with
+/// [`Feature::get_object`](super::Feature::get_object).
+pub trait ObjectType: ParamType {
+	/// The type of the object pointed to. Its size is validated by the SDK when the pointer is read.
+	type Object;
+}
+
+macro_rules! impl_object_type {
+	($($ty:ty => $object:ty;)*) => {
+		$(impl ObjectType for $ty {
+			type Object = $object;
+		})*
+	};
+}
+
+impl_object_type! {
+	Image => crate::cv::sys::NvCVImage;
+	[Point2D] => Point2D;
+	[Point3D] => Point3D;
+	[Quaternion] => Quaternion;
+	Quaternion => Quaternion;
+	Vector3 => Vector3;
+	RenderingParams => RenderingParams;
+	sys::NvAR_BBoxes => sys::NvAR_BBoxes;
+	sys::NvAR_TrackingBBoxes => sys::NvAR_TrackingBBoxes;
+	sys::NvAR_FaceMesh => sys::NvAR_FaceMesh;
+}
+
+/// The name of a feature parameter, along with the type of value it holds.
+///
+/// The constants on this type cover the parameters used by the features in this crate, e.g.
+/// [`Param::<u32>::CONFIG_LANDMARKS_SIZE`](Param::CONFIG_LANDMARKS_SIZE). Using a constant with a value of the wrong
+/// type fails to compile.
+pub struct Param<T: ?Sized> {
+	name: &'static CStr,
+	_marker: PhantomData<fn() -> *const T>
+}
+
+impl<T: ?Sized> Param<T> {
+	/// Creates a parameter from its full name, e.g. `NvAR_Parameter_Config_Landmarks_Size`.
+	///
+	/// # Safety
+	/// The SDK must expect a value of type `T` for the parameter `name`. Binding a buffer of the wrong type to an input
+	/// or output lets the SDK read or write out of bounds.
+	pub const unsafe fn new(name: &'static CStr) -> Self {
+		Param { name, _marker: PhantomData }
+	}
+
+	#[inline]
+	pub const fn name(&self) -> &'static CStr {
+		self.name
+	}
+}
+
+impl<T: ?Sized> Clone for Param<T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: ?Sized> Copy for Param<T> {}
+
+impl<T: ?Sized> fmt::Debug for Param<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("Param").field(&self.name).finish()
+	}
+}
+
+macro_rules! params {
+	($($ty:ty {
+		$($(#[$attr:meta])* $konst:ident = $name:literal;)*
+	})*) => {
+		$(impl Param<$ty> {
+			$($(#[$attr])* pub const $konst: Self = unsafe { Param::new($name) };)*
+		})*
+	};
+}
+
+params! {
+	u32 {
+		/// All features. `0` selects the higher quality model, `1` the higher performance model.
+		CONFIG_MODE = c"NvAR_Parameter_Config_Mode";
+		/// All features.
+		CONFIG_USE_CUDA_GRAPH = c"NvAR_Parameter_Config_UseCudaGraph";
+		/// All features. A [`TemporalFilter`](super::TemporalFilter) bitmask for face features, or a boolean for body
+		/// features.
+		CONFIG_TEMPORAL = c"NvAR_Parameter_Config_Temporal";
+		/// `LandmarkDetection`, `Face3DReconstruction`, `FaceExpressions` & `GazeRedirection`.
+		CONFIG_LANDMARKS_SIZE = c"NvAR_Parameter_Config_Landmarks_Size";
+		/// `Face3DReconstruction` & `FaceExpressions`. Read-only.
+		CONFIG_EXPRESSION_COUNT = c"NvAR_Parameter_Config_ExpressionCount";
+		/// `Face3DReconstruction`. Read-only.
+		CONFIG_SHAPE_EIGEN_VALUE_COUNT = c"NvAR_Parameter_Config_ShapeEigenValueCount";
+		/// `Face3DReconstruction`. Read-only.
+		CONFIG_VERTEX_COUNT = c"NvAR_Parameter_Config_VertexCount";
+		/// `Face3DReconstruction`. Read-only.
+		CONFIG_TRIANGLE_COUNT = c"NvAR_Parameter_Config_TriangleCount";
+		/// `FaceExpressions`.
+		CONFIG_POSE_MODE = c"NvAR_Parameter_Config_PoseMode";
+		/// `FaceExpressions`.
+		CONFIG_ENABLE_CHEEK_PUFF = c"NvAR_Parameter_Config_EnableCheekPuff";
+		/// `BodyPoseEstimation`. Read-only.
+		CONFIG_NUM_KEY_POINTS = c"NvAR_Parameter_Config_NumKeyPoints";
+		/// `BodyPoseEstimation`.
+		CONFIG_TRACK_PEOPLE = c"NvAR_Parameter_Config_TrackPeople";
+		/// `BodyPoseEstimation`.
+		CONFIG_SHADOW_TRACKING_AGE = c"NvAR_Parameter_Config_ShadowTrackingAge";
+		/// `BodyPoseEstimation`.
+		CONFIG_PROBATION_AGE = c"NvAR_Parameter_Config_ProbationAge";
+		/// `BodyPoseEstimation`.
+		CONFIG_MAX_TARGETS_TRACKED = c"NvAR_Parameter_Config_MaxTargetsTracked";
+		/// `GazeRedirection`.
+		CONFIG_GAZE_REDIRECT = c"NvAR_Parameter_Config_GazeRedirect";
+		/// `GazeRedirection`.
+		CONFIG_EYE_SIZE_SENSITIVITY = c"NvAR_Parameter_Config_EyeSizeSensitivity";
+		/// `Face3DReconstruction` & `GazeRedirection`.
+		INPUT_WIDTH = c"NvAR_Parameter_Input_Width";
+		/// `Face3DReconstruction` & `GazeRedirection`.
+		INPUT_HEIGHT = c"NvAR_Parameter_Input_Height";
+	}
+	f32 {
+		/// `BodyPoseEstimation`.
+		CONFIG_FOCAL_LENGTH = c"NvAR_Parameter_Config_FocalLength";
+	}
+	str {
+		/// All features. Read-only.
+		CONFIG_FEATURE_DESCRIPTION = c"NvAR_Parameter_Config_FeatureDescription";
+		/// All features. The directory containing the feature's models.
+		CONFIG_MODEL_DIR = c"NvAR_Parameter_Config_ModelDir";
+		/// `Face3DReconstruction`.
+		CONFIG_FACE_MODEL = c"NvAR_Parameter_Config_FaceModel";
+	}
+	CUDAStream {
+		/// All features.
+		CONFIG_CUDA_STREAM = c"NvAR_Parameter_Config_CUDAStream";
+	}
+	Image {
+		/// All features.
+		INPUT_IMAGE = c"NvAR_Parameter_Input_Image";
+		/// `GazeRedirection`.
+		OUTPUT_IMAGE = c"NvAR_Parameter_Output_Image";
+	}
+	sys::NvAR_BBoxes {
+		/// `LandmarkDetection`.
+		INPUT_BOUNDING_BOXES = c"NvAR_Parameter_Input_BoundingBoxes";
+		/// All features.
+		OUTPUT_BOUNDING_BOXES = c"NvAR_Parameter_Output_BoundingBoxes";
+	}
+	sys::NvAR_TrackingBBoxes {
+		/// `BodyPoseEstimation`, in multi-person mode.
+		OUTPUT_TRACKING_BOUNDING_BOXES = c"NvAR_Parameter_Output_TrackingBoundingBoxes";
+	}
+	sys::NvAR_FaceMesh {
+		/// `Face3DReconstruction`.
+		OUTPUT_FACE_MESH = c"NvAR_Parameter_Output_FaceMesh";
+	}
+	RenderingParams {
+		/// `Face3DReconstruction`.
+		OUTPUT_RENDERING_PARAMS = c"NvAR_Parameter_Output_RenderingParams";
+	}
+	Quaternion {
+		/// `LandmarkDetection`, `Face3DReconstruction` & `FaceExpressions`.
+		OUTPUT_POSE = c"NvAR_Parameter_Output_Pose";
+		/// `GazeRedirection`.
+		OUTPUT_HEAD_POSE = c"NvAR_Parameter_Output_HeadPose";
+	}
+	Vector3 {
+		/// `FaceExpressions`.
+		OUTPUT_POSE_TRANSLATION = c"NvAR_Parameter_Output_PoseTranslation";
+	}
+	[f32] {
+		/// All features.
+		OUTPUT_BOUNDING_BOXES_CONFIDENCE = c"NvAR_Parameter_Output_BoundingBoxesConfidence";
+		/// `LandmarkDetection`, `Face3DReconstruction`, `FaceExpressions` & `GazeRedirection`.
+		OUTPUT_LANDMARKS_CONFIDENCE = c"NvAR_Parameter_Output_LandmarksConfidence";
+		/// `Face3DReconstruction` & `FaceExpressions`.
+		OUTPUT_EXPRESSION_COEFFICIENTS = c"NvAR_Parameter_Output_ExpressionCoefficients";
+		/// `Face3DReconstruction`.
+		OUTPUT_SHAPE_EIGEN_VALUES = c"NvAR_Parameter_Output_ShapeEigenValues";
+		/// `BodyPoseEstimation`.
+		OUTPUT_KEY_POINTS_CONFIDENCE = c"NvAR_Parameter_Output_KeyPointsConfidence";
+		/// `GazeRedirection`. The gaze pitch & yaw.
+		OUTPUT_GAZE_VECTOR = c"NvAR_Parameter_Output_OutputGazeVector";
+		/// `GazeRedirection`. The head translation as `[x, y, z]`.
+		OUTPUT_HEAD_TRANSLATION = c"NvAR_Parameter_Output_OutputHeadTranslation";
+	}
+	[Point2D] {
+		/// `LandmarkDetection`, `Face3DReconstruction`, `FaceExpressions` & `GazeRedirection`.
+		OUTPUT_LANDMARKS = c"NvAR_Parameter_Output_Landmarks";
+		/// `BodyPoseEstimation`.
+		OUTPUT_KEY_POINTS = c"NvAR_Parameter_Output_KeyPoints";
+	}
+	[Point3D] {
+		/// `BodyPoseEstimation`. Read-only.
+		CONFIG_REFERENCE_POSE = c"NvAR_Parameter_Config_ReferencePose";
+		/// `BodyPoseEstimation`.
+		OUTPUT_KEY_POINTS_3D = c"NvAR_Parameter_Output_KeyPoints3D";
+		/// `GazeRedirection`.
+		OUTPUT_GAZE_DIRECTION = c"NvAR_Parameter_Output_GazeDirection";
+	}
+	[Quaternion] {
+		/// `BodyPoseEstimation`.
+		OUTPUT_JOINT_ANGLES = c"NvAR_Parameter_Output_JointAngles";
+	}
+}
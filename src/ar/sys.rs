@@ -13,7 +13,8 @@ use std::{
 };
 
 use bitflags::bitflags;
-use libloading::Library;
+
+use crate::macros::define_api;
 
 #[repr(C)]
 #[derive(Default, Debug, Clone)]
@@ -48,6 +49,7 @@ pub struct NvAR_BBoxes {
 }
 
 #[repr(C)]
+#[derive(Default, Debug, Clone)]
 pub struct NvAR_TrackingBBox {
     pub bbox: NvAR_Rect,
     pub tracking_id: u16,
@@ -123,6 +125,7 @@ pub struct NvAR_Vector2f {
 }
 
 #[repr(C)]
+#[derive(Default, Debug, Clone)]
 pub struct NvAR_RenderingParams {
     pub frustum: NvAR_Frustum,
     pub rotation: NvAR_Quaternion,
@@ -157,70 +160,94 @@ pub type CUstream = *mut ();
 pub struct NvAR_Feature;
 pub type NvAR_FeatureHandle = *mut NvAR_Feature;
 
-pub unsafe fn NvAR_GetVersion(library: &Library) -> crate::Result<(u8, u8, u8)> {
-    let sym = library.get::<unsafe extern "C" fn(*mut u32) -> i32>(b"NvAR_GetVersion")?;
+define_api! {
+    pub struct NvAR_Api {
+        fn NvAR_GetVersion(*mut u32) -> i32;
+        fn NvAR_Create(*const c_char, *mut NvAR_FeatureHandle) -> i32;
+        fn NvAR_Load(NvAR_FeatureHandle) -> i32;
+        fn NvAR_Run(NvAR_FeatureHandle) -> i32;
+        fn NvAR_Destroy(NvAR_FeatureHandle) -> i32;
+        fn NvAR_CudaStreamCreate(*mut CUstream) -> i32;
+        fn NvAR_CudaStreamDestroy(CUstream) -> i32;
+        fn NvAR_SetU32(NvAR_FeatureHandle, *const c_char, u32) -> i32;
+        fn NvAR_SetS32(NvAR_FeatureHandle, *const c_char, i32) -> i32;
+        fn NvAR_SetF32(NvAR_FeatureHandle, *const c_char, f32) -> i32;
+        fn NvAR_SetF64(NvAR_FeatureHandle, *const c_char, f64) -> i32;
+        fn NvAR_SetU64(NvAR_FeatureHandle, *const c_char, u64) -> i32;
+        fn NvAR_SetString(NvAR_FeatureHandle, *const c_char, *const c_char) -> i32;
+        fn NvAR_SetCudaStream(NvAR_FeatureHandle, *const c_char, CUstream) -> i32;
+        fn NvAR_SetObject(NvAR_FeatureHandle, *const c_char, *mut c_void, u32) -> i32;
+        fn NvAR_SetF32Array(NvAR_FeatureHandle, *const c_char, *mut f32, i32) -> i32;
+        fn NvAR_GetU32(NvAR_FeatureHandle, *const c_char, *mut u32) -> i32;
+        fn NvAR_GetS32(NvAR_FeatureHandle, *const c_char, *mut i32) -> i32;
+        fn NvAR_GetF32(NvAR_FeatureHandle, *const c_char, *mut f32) -> i32;
+        fn NvAR_GetF64(NvAR_FeatureHandle, *const c_char, *mut f64) -> i32;
+        fn NvAR_GetU64(NvAR_FeatureHandle, *const c_char, *mut u64) -> i32;
+        fn NvAR_GetString(NvAR_FeatureHandle, *const c_char, *mut *const c_char) -> i32;
+        fn NvAR_GetCudaStream(NvAR_FeatureHandle, *const c_char, *mut CUstream) -> i32;
+        fn NvAR_GetObject(NvAR_FeatureHandle, *const c_char, *mut *const c_void, u32) -> i32;
+        fn NvAR_GetF32Array(NvAR_FeatureHandle, *const c_char, *mut *const f32, *mut i32) -> i32;
+
+        optional {}
+    }
+}
+
+/// Returns the SDK version, packed as `major << 24 | minor << 16 | release << 8 | build`.
+pub unsafe fn NvAR_GetVersion(api: &NvAR_Api) -> crate::Result<u32> {
     let mut out = 0u32;
-    crate::error::to_status(sym(&mut out))?;
-    Ok((
-        (out >> 24 & 0xFF) as u8,
-        (out >> 16 & 0xFF) as u8,
-        (out >> 8 & 0xFF) as u8,
-    ))
+    crate::error::to_status("NvAR_GetVersion", api.NvAR_GetVersion()?(&mut out))?;
+    Ok(out)
 }
 
 pub unsafe fn NvAR_Create(
-    library: &Library,
+    api: &NvAR_Api,
     feature: *const c_char,
 ) -> crate::Result<NvAR_FeatureHandle> {
-    let sym = library.get::<unsafe extern "C" fn(*const c_char, *mut NvAR_FeatureHandle) -> i32>(
-        b"NvAR_Create",
-    )?;
     let mut feature_handle = ptr::null_mut();
-    crate::error::to_status(sym(feature, &mut feature_handle))?;
+    crate::error::to_status(
+        "NvAR_Create",
+        api.NvAR_Create()?(feature, &mut feature_handle),
+    )?;
     Ok(feature_handle)
 }
 
-pub unsafe fn NvAR_Load(library: &Library, handle: NvAR_FeatureHandle) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(NvAR_FeatureHandle) -> i32>(b"NvAR_Load")?;
-    crate::error::to_status(sym(handle))
+pub unsafe fn NvAR_Load(api: &NvAR_Api, handle: NvAR_FeatureHandle) -> crate::Result<()> {
+    crate::error::to_status("NvAR_Load", api.NvAR_Load()?(handle))
 }
 
-pub unsafe fn NvAR_Run(library: &Library, handle: NvAR_FeatureHandle) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(NvAR_FeatureHandle) -> i32>(b"NvAR_Run")?;
-    crate::error::to_status(sym(handle))
+pub unsafe fn NvAR_Run(api: &NvAR_Api, handle: NvAR_FeatureHandle) -> crate::Result<()> {
+    crate::error::to_status("NvAR_Run", api.NvAR_Run()?(handle))
 }
 
-pub unsafe fn NvAR_Destroy(library: &Library, handle: NvAR_FeatureHandle) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(NvAR_FeatureHandle) -> i32>(b"NvAR_Destroy")?;
-    crate::error::to_status(sym(handle))
+pub unsafe fn NvAR_Destroy(api: &NvAR_Api, handle: NvAR_FeatureHandle) -> crate::Result<()> {
+    crate::error::to_status("NvAR_Destroy", api.NvAR_Destroy()?(handle))
 }
 
-pub unsafe fn NvAR_CudaStreamCreate(library: &Library) -> crate::Result<CUstream> {
-    let sym =
-        library.get::<unsafe extern "C" fn(*mut CUstream) -> i32>(b"NvAR_CudaStreamCreate")?;
+pub unsafe fn NvAR_CudaStreamCreate(api: &NvAR_Api) -> crate::Result<CUstream> {
     let mut out = ptr::null_mut();
-    crate::error::to_status(sym(&mut out))?;
+    crate::error::to_status(
+        "NvAR_CudaStreamCreate",
+        api.NvAR_CudaStreamCreate()?(&mut out),
+    )?;
     Ok(out)
 }
 
-pub unsafe fn NvAR_CudaStreamDestroy(library: &Library, stream: CUstream) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(CUstream) -> i32>(b"NvAR_CudaStreamDestroy")?;
-    crate::error::to_status(sym(stream))
+pub unsafe fn NvAR_CudaStreamDestroy(api: &NvAR_Api, stream: CUstream) -> crate::Result<()> {
+    crate::error::to_status(
+        "NvAR_CudaStreamDestroy",
+        api.NvAR_CudaStreamDestroy()?(stream),
+    )
 }
 
 macro_rules! define_set {
     ($(pub unsafe fn $name:ident = $ty:ty;)+) => {
         $(pub unsafe fn $name(
-            library: &Library,
+            api: &NvAR_Api,
             handle: NvAR_FeatureHandle,
             name: *const c_char,
             val: $ty,
         ) -> crate::Result<()> {
-            let sym = library
-                .get::<unsafe extern "C" fn(NvAR_FeatureHandle, *const c_char, $ty) -> i32>(
-                    stringify!($name).as_bytes(),
-                )?;
-            crate::error::to_status(sym(handle, name, val))
+            crate::error::to_param_status(stringify!($name), name, api.$name()?(handle, name, val))
         })+
     };
 }
@@ -236,52 +263,40 @@ define_set!(
 );
 
 pub unsafe fn NvAR_SetObject<T>(
-    library: &Library,
+    api: &NvAR_Api,
     handle: NvAR_FeatureHandle,
     name: *const c_char,
     ptr: *mut T,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        NvAR_FeatureHandle,
-        *const c_char,
-        *mut c_void,
-        u32,
-    ) -> i32>(b"NvAR_SetObject")?;
-    crate::error::to_status(sym(
-        handle,
+    crate::error::to_param_status(
+        "NvAR_SetObject",
         name,
-        ptr.cast(),
-        std::mem::size_of::<T>() as u32,
-    ))
+        api.NvAR_SetObject()?(handle, name, ptr.cast(), std::mem::size_of::<T>() as u32),
+    )
 }
 
 pub unsafe fn NvAR_SetF32Array(
-    library: &Library,
+    api: &NvAR_Api,
     handle: NvAR_FeatureHandle,
     name: *const c_char,
     vals: &mut [f32],
 ) -> crate::Result<()> {
-    let sym =
-        library
-            .get::<unsafe extern "C" fn(NvAR_FeatureHandle, *const c_char, *mut f32, i32) -> i32>(
-                b"NvAR_SetF32Array",
-            )?;
-    crate::error::to_status(sym(handle, name, vals.as_mut_ptr(), vals.len() as i32))
+    crate::error::to_param_status(
+        "NvAR_SetF32Array",
+        name,
+        api.NvAR_SetF32Array()?(handle, name, vals.as_mut_ptr(), vals.len() as i32),
+    )
 }
 
 macro_rules! define_get {
     ($(pub unsafe fn $name:ident: $ty:ty = $def:expr;)+) => {
         $(pub unsafe fn $name(
-            library: &Library,
+            api: &NvAR_Api,
             handle: NvAR_FeatureHandle,
             name: *const c_char,
         ) -> crate::Result<$ty> {
-            let sym = library
-                .get::<unsafe extern "C" fn(NvAR_FeatureHandle, *const c_char, *mut $ty) -> i32>(
-                    stringify!($name).as_bytes(),
-                )?;
             let mut val = $def;
-            crate::error::to_status(sym(handle, name, &mut val))?;
+            crate::error::to_param_status(stringify!($name), name, api.$name()?(handle, name, &mut val))?;
             Ok(val)
         })+
     };
@@ -298,34 +313,33 @@ define_get!(
 );
 
 pub unsafe fn NvAR_GetObject<T>(
-    library: &Library,
+    api: &NvAR_Api,
     handle: NvAR_FeatureHandle,
     name: *const c_char,
 ) -> crate::Result<*const T> {
-    let sym = library.get::<unsafe extern "C" fn(
-        NvAR_FeatureHandle,
-        *const c_char,
-        *mut *const c_void,
-        u32,
-    ) -> i32>(b"NvAR_GetObject")?;
     let mut val = ptr::null();
-    crate::error::to_status(sym(handle, name, &mut val, std::mem::size_of::<T>() as u32))?;
+    crate::error::to_param_status(
+        "NvAR_GetObject",
+        name,
+        api.NvAR_GetObject()?(handle, name, &mut val, std::mem::size_of::<T>() as u32),
+    )?;
     Ok(val.cast())
 }
 
 pub unsafe fn NvAR_GetF32Array<'v>(
-    library: &Library,
+    api: &NvAR_Api,
     handle: NvAR_FeatureHandle,
     name: *const c_char,
 ) -> crate::Result<&'v [f32]> {
-    let sym = library.get::<unsafe extern "C" fn(
-        NvAR_FeatureHandle,
-        *const c_char,
-        *mut *const f32,
-        *mut i32,
-    ) -> i32>(b"NvAR_GetF32Array")?;
     let mut val = ptr::null();
     let mut len = 0;
-    crate::error::to_status(sym(handle, name, &mut val, &mut len))?;
+    crate::error::to_param_status(
+        "NvAR_GetF32Array",
+        name,
+        api.NvAR_GetF32Array()?(handle, name, &mut val, &mut len),
+    )?;
+    if val.is_null() {
+        return Ok(&[]);
+    }
     Ok(std::slice::from_raw_parts(val, len as usize))
 }
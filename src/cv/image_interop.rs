@@ -0,0 +1,73 @@
+use std::num::NonZeroUsize;
+
+use image::{DynamicImage, GrayImage, ImageBuffer, RgbImage, RgbaImage};
+
+use super::{ComponentType, Image, ImageLayout, ImageRef, ImageView, MemorySpace, PixelFormat};
+use crate::{Error, Result};
+
+macro_rules! impl_from_image_buffer {
+	($($buffer:ty => $format:ident),*) => {
+		$(
+			/// Borrows the image's pixels without copying, using the [global runtime](crate::Runtime::global).
+			impl<'i> TryFrom<&'i mut $buffer> for ImageView<'i> {
+				type Error = Error;
+
+				fn try_from(image: &'i mut $buffer) -> Result<Self> {
+					let (width, height) = image.dimensions();
+					ImageView::new_rgb(width, height, PixelFormat::$format, image)
+				}
+			}
+
+			/// Borrows the image's pixels without copying, using the [global runtime](crate::Runtime::global).
+			impl<'i> TryFrom<&'i $buffer> for ImageRef<'i> {
+				type Error = Error;
+
+				fn try_from(image: &'i $buffer) -> Result<Self> {
+					let (width, height) = image.dimensions();
+					ImageRef::new_rgb(width, height, PixelFormat::$format, image)
+				}
+			}
+		)*
+	};
+}
+
+impl_from_image_buffer!(RgbImage => RGB, RgbaImage => RGBA, GrayImage => Y);
+
+impl Image {
+	/// Copies the image into an owned [`DynamicImage`], downloading it from the GPU if necessary.
+	///
+	/// Grayscale images are converted to [`DynamicImage::ImageLuma8`], images with an alpha channel to
+	/// [`DynamicImage::ImageRgba8`], and all others to [`DynamicImage::ImageRgb8`]. Floating point components are
+	/// expected to be in the range `0.0..=1.0`.
+	pub fn to_dynamic_image(&self) -> Result<DynamicImage> {
+		let format = match self.pixel_format() {
+			PixelFormat::Y | PixelFormat::A => PixelFormat::Y,
+			PixelFormat::YA | PixelFormat::RGBA | PixelFormat::BGRA | PixelFormat::ARGB | PixelFormat::ABGR => PixelFormat::RGBA,
+			_ => PixelFormat::RGB
+		};
+		let scale = match self.component_type() {
+			ComponentType::F16 | ComponentType::F32 | ComponentType::F64 => 255.0,
+			_ => 1.0
+		};
+
+		let mut cpu = Image::new_in(
+			self.runtime(),
+			self.width(),
+			self.height(),
+			format,
+			ComponentType::U8,
+			ImageLayout::Interleaved,
+			MemorySpace::CPU,
+			NonZeroUsize::new(1)
+		)?;
+		self.scale_to(&mut cpu, scale)?;
+		let pixels: Vec<u8> = cpu.rows::<u8>()?.flatten().copied().collect();
+
+		let (width, height) = (self.width(), self.height());
+		Ok(match format {
+			PixelFormat::Y => DynamicImage::ImageLuma8(ImageBuffer::from_raw(width, height, pixels).expect("buffer is sized for the image")),
+			PixelFormat::RGBA => DynamicImage::ImageRgba8(ImageBuffer::from_raw(width, height, pixels).expect("buffer is sized for the image")),
+			_ => DynamicImage::ImageRgb8(ImageBuffer::from_raw(width, height, pixels).expect("buffer is sized for the image"))
+		})
+	}
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "image")]
+mod image_interop;
+mod pixels;
 pub mod sys;
 
 use std::{
@@ -5,14 +8,32 @@ use std::{
 	marker::PhantomData,
 	num::NonZeroUsize,
 	ops::Deref,
-	ptr::{self, NonNull}
+	ptr::{self, NonNull},
+	sync::Arc
 };
 
-pub use self::sys::{
-	NvCVImage_ColorSpace as ColorSpace, NvCVImage_ComponentType as ComponentType, NvCVImage_Layout as ImageLayout, NvCVImage_MemorySpace as MemorySpace,
-	NvCVImage_PixelFormat as PixelFormat
+pub use self::{
+	pixels::Component,
+	sys::{
+		NvCVImage_ColorSpace as ColorSpace, NvCVImage_ComponentType as ComponentType, NvCVImage_Layout as ImageLayout, NvCVImage_MemorySpace as MemorySpace,
+		NvCVImage_PixelFormat as PixelFormat
+	}
 };
-use crate::{Result, ar::CUDAStream};
+use crate::{Error, Result, Runtime, ar::CUDAStream};
+
+impl PixelFormat {
+	/// Returns the number of components in each pixel, or `None` for YUV & unknown formats.
+	#[inline]
+	pub fn num_components(&self) -> Option<usize> {
+		match self {
+			PixelFormat::Y | PixelFormat::A => Some(1),
+			PixelFormat::YA => Some(2),
+			PixelFormat::RGB | PixelFormat::BGR => Some(3),
+			PixelFormat::RGBA | PixelFormat::BGRA | PixelFormat::ARGB | PixelFormat::ABGR => Some(4),
+			PixelFormat::Unknown | PixelFormat::YUV420 | PixelFormat::YUV422 | PixelFormat::YUV444 => None
+		}
+	}
+}
 
 impl ImageLayout {
 	#[inline]
@@ -26,13 +47,9 @@ impl ImageLayout {
 	}
 }
 
-enum ImageInner {
-	Allocated(*mut sys::NvCVImage),
-	Owned(Box<sys::NvCVImage>)
-}
-
 pub struct Image {
-	inner: ImageInner,
+	inner: Box<sys::NvCVImage>,
+	runtime: Arc<Runtime>,
 	drop: bool
 }
 
@@ -47,11 +64,25 @@ impl Image {
 		layout: ImageLayout,
 		memory_space: MemorySpace,
 		alignment: Option<NonZeroUsize>
+	) -> Result<Image> {
+		Image::new_in(Runtime::global()?, width, height, format, r#type, layout, memory_space, alignment)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn new_in(
+		runtime: &Arc<Runtime>,
+		width: u32,
+		height: u32,
+		format: PixelFormat,
+		r#type: ComponentType,
+		layout: ImageLayout,
+		memory_space: MemorySpace,
+		alignment: Option<NonZeroUsize>
 	) -> Result<Image> {
 		let mut img = sys::NvCVImage::default();
 		unsafe {
 			sys::NvCVImage_Alloc(
-				crate::nvcv_lib_handle(),
+				runtime.nvcv_api(),
 				&mut img,
 				width,
 				height,
@@ -63,16 +94,15 @@ impl Image {
 			)
 		}?;
 		Ok(Image {
-			inner: ImageInner::Owned(Box::new(img)),
+			inner: Box::new(img),
+			runtime: Arc::clone(runtime),
 			drop: true
 		})
 	}
 
-	pub(crate) fn from_ptr(ptr: *mut sys::NvCVImage, drop: bool) -> Self {
-		Image {
-			inner: ImageInner::Allocated(ptr),
-			drop
-		}
+	#[inline]
+	pub fn runtime(&self) -> &Arc<Runtime> {
+		&self.runtime
 	}
 
 	#[inline]
@@ -82,10 +112,7 @@ impl Image {
 
 	#[inline]
 	pub(crate) fn as_ptr(&self) -> *mut sys::NvCVImage {
-		match &self.inner {
-			ImageInner::Allocated(p) => *p,
-			ImageInner::Owned(p) => (p.as_ref() as *const sys::NvCVImage).cast_mut()
-		}
+		(self.inner.as_ref() as *const sys::NvCVImage).cast_mut()
 	}
 
 	#[inline]
@@ -108,13 +135,6 @@ impl Image {
 		NonNull::new(self.ref_inner().pixels)
 	}
 
-	#[inline]
-	pub fn pixels(&self) -> &[u8] {
-		unsafe {
-			std::slice::from_raw_parts(self.pixels_ptr().unwrap().as_ptr().cast(), (self.height() as isize * self.stride()) as usize * self.pixel_bytes())
-		}
-	}
-
 	#[inline]
 	pub fn pixel_format(&self) -> PixelFormat {
 		self.ref_inner().pixel_format
@@ -156,23 +176,23 @@ impl Image {
 	}
 
 	pub fn transfer_to(&self, dst: &mut Image) -> Result<()> {
-		unsafe { sys::NvCVImage_Transfer(crate::nvcv_lib_handle(), self.as_ptr().cast_const(), dst.as_ptr(), 1.0, ptr::null_mut(), ptr::null_mut()) }?;
+		unsafe { sys::NvCVImage_Transfer(self.runtime.nvcv_api(), self.as_ptr().cast_const(), dst.as_ptr(), 1.0, ptr::null_mut(), ptr::null_mut()) }?;
 		Ok(())
 	}
 
 	pub fn scale_to(&self, dst: &mut Image, scale: f32) -> Result<()> {
-		unsafe { sys::NvCVImage_Transfer(crate::nvcv_lib_handle(), self.as_ptr().cast_const(), dst.as_ptr(), scale, ptr::null_mut(), ptr::null_mut()) }?;
+		unsafe { sys::NvCVImage_Transfer(self.runtime.nvcv_api(), self.as_ptr().cast_const(), dst.as_ptr(), scale, ptr::null_mut(), ptr::null_mut()) }?;
 		Ok(())
 	}
 
 	pub fn transfer_to_opt(&self, dst: &mut Image, scale: f32, stream: Option<&CUDAStream>, tmp: Option<&mut Image>) -> Result<()> {
 		unsafe {
 			sys::NvCVImage_Transfer(
-				crate::nvcv_lib_handle(),
+				self.runtime.nvcv_api(),
 				self.as_ptr().cast_const(),
 				dst.as_ptr(),
 				scale,
-				stream.map(|c| c.0).unwrap_or_else(ptr::null_mut),
+				stream.map(|c| c.as_raw()).unwrap_or_else(ptr::null_mut),
 				tmp.map(|c| c.as_ptr()).unwrap_or_else(ptr::null_mut)
 			)
 		}?;
@@ -180,10 +200,11 @@ impl Image {
 	}
 
 	pub fn view_rect(&self, x: u32, y: u32, width: u32, height: u32) -> Result<Image> {
-		let view = Image::new(width, height, self.pixel_format(), self.component_type(), self.layout(), self.memory_space(), NonZeroUsize::new(1))?;
+		let view =
+			Image::new_in(&self.runtime, width, height, self.pixel_format(), self.component_type(), self.layout(), self.memory_space(), NonZeroUsize::new(1))?;
 		unsafe {
 			sys::NvCVImage_TransferRect(
-				crate::nvcv_lib_handle(),
+				self.runtime.nvcv_api(),
 				self.as_ptr(),
 				&sys::NvCVRect2i {
 					x: x as i32,
@@ -206,11 +227,12 @@ impl Image {
 		assert!(self.height() > y + height);
 
 		let mut view = sys::NvCVImage::default();
-		unsafe { sys::NvCVImage_InitView(crate::nvcv_lib_handle(), &mut view, self.as_ptr(), x as i32, y as i32, width, height) }?;
+		unsafe { sys::NvCVImage_InitView(self.runtime.nvcv_api(), &mut view, self.as_ptr(), x as i32, y as i32, width, height) }?;
 		dbg!(view.width, view.height, view.pixel_bytes, view.pitch);
 		Ok(ImageView {
 			image: Image {
-				inner: ImageInner::Owned(Box::new(view)),
+				inner: Box::new(view),
+				runtime: Arc::clone(&self.runtime),
 				drop: true
 			},
 			_phantom: PhantomData
@@ -221,7 +243,7 @@ impl Image {
 impl Clone for Image {
 	fn clone(&self) -> Self {
 		let inf = self.ref_inner();
-		let mut new = Image::new(inf.width, inf.height, inf.pixel_format, inf.component_type, inf.planar, inf.gpu_mem, None)
+		let mut new = Image::new_in(&self.runtime, inf.width, inf.height, inf.pixel_format, inf.component_type, inf.planar, inf.gpu_mem, None)
 			.expect("Failed to allocate new Image buffer");
 		self.transfer_to(&mut new).expect("Failed to transfer image data to new buffer");
 		new
@@ -235,14 +257,7 @@ impl Clone for Image {
 impl Drop for Image {
 	fn drop(&mut self) {
 		if self.drop {
-			match &mut self.inner {
-				ImageInner::Allocated(p) => {
-					unsafe { sys::NvCVImage_Destroy(crate::nvcv_lib_handle(), *p) }.unwrap();
-				}
-				ImageInner::Owned(p) => {
-					unsafe { sys::NvCVImage_Dealloc(crate::nvcv_lib_handle(), p.as_mut()) }.unwrap();
-				}
-			}
+			unsafe { sys::NvCVImage_Dealloc(self.runtime.nvcv_api(), self.inner.as_mut()) }.unwrap();
 		}
 	}
 }
@@ -253,28 +268,124 @@ pub struct ImageView<'i> {
 }
 
 impl<'i> ImageView<'i> {
-	pub fn new_rgb(width: u32, height: u32, format: PixelFormat, data: &'i mut [u8]) -> ImageView<'i> {
-		let mut image = sys::NvCVImage::default();
-		image.width = width;
-		image.height = height;
-		image.pixel_format = format;
-		image.num_components = 3;
-		image.pixel_bytes = 3;
-		image.component_bytes = 1;
-		image.component_type = ComponentType::U8;
-		image.color_space = ColorSpace::empty();
-		image.pitch = width as i32 * 3;
-		image.gpu_mem = MemorySpace::CPU;
-		image.pixels = data.as_mut_ptr().cast();
-		image.planar = ImageLayout::Interleaved;
-		ImageView {
+	/// Creates a view over tightly packed, interleaved 8-bit pixels in CPU memory, e.g. an RGB or RGBA frame.
+	pub fn new_rgb(width: u32, height: u32, format: PixelFormat, data: &'i mut [u8]) -> Result<ImageView<'i>> {
+		ImageView::new_rgb_in(Runtime::global()?, width, height, format, data)
+	}
+
+	pub fn new_rgb_in(runtime: &Arc<Runtime>, width: u32, height: u32, format: PixelFormat, data: &'i mut [u8]) -> Result<ImageView<'i>> {
+		ImageView::from_raw_parts_in(
+			runtime,
+			width,
+			height,
+			packed_pitch(width, format)?,
+			format,
+			ComponentType::U8,
+			ImageLayout::Interleaved,
+			MemorySpace::CPU,
+			data
+		)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn from_raw_parts(
+		width: u32,
+		height: u32,
+		pitch: i32,
+		format: PixelFormat,
+		component_type: ComponentType,
+		layout: ImageLayout,
+		memory_space: MemorySpace,
+		data: &'i mut [u8]
+	) -> Result<ImageView<'i>> {
+		ImageView::from_raw_parts_in(Runtime::global()?, width, height, pitch, format, component_type, layout, memory_space, data)
+	}
+
+	/// Creates a view over the pixels in `data`, whose rows start every `pitch` bytes. A negative pitch stores the
+	/// rows bottom-up, with the top row at the end of `data`.
+	///
+	/// The pixel & component sizes are derived from `format` & `component_type`. Returns an error if `data` is too
+	/// small to hold the image, if `memory_space` is not [`MemorySpace::CPU`] or [`MemorySpace::CPUPinned`], or if
+	/// `layout` is a YUV planar layout, which is not yet supported.
+	#[allow(clippy::too_many_arguments)]
+	pub fn from_raw_parts_in(
+		runtime: &Arc<Runtime>,
+		width: u32,
+		height: u32,
+		pitch: i32,
+		format: PixelFormat,
+		component_type: ComponentType,
+		layout: ImageLayout,
+		memory_space: MemorySpace,
+		data: &'i mut [u8]
+	) -> Result<ImageView<'i>> {
+		let image = init_view(runtime, width, height, pitch, format, component_type, layout, memory_space, data.len(), data.as_mut_ptr())?;
+		Ok(ImageView {
 			image: Image {
-				inner: ImageInner::Owned(Box::new(image)),
+				inner: Box::new(image),
+				runtime: Arc::clone(runtime),
 				drop: false
 			},
 			_phantom: PhantomData
-		}
+		})
+	}
+}
+
+/// Returns the pitch of a tightly packed row of interleaved 8-bit pixels.
+fn packed_pitch(width: u32, format: PixelFormat) -> Result<i32> {
+	let num_components = format
+		.num_components()
+		.ok_or(Error::InvalidImage("pixel format is not an interleaved RGB or grayscale format"))?;
+	i32::try_from(width as usize * num_components).map_err(|_| Error::InvalidImage("image is too wide"))
+}
+
+/// Initializes a descriptor for a borrowed buffer of `len` bytes starting at `data`, validating that the image fits.
+#[allow(clippy::too_many_arguments)]
+fn init_view(
+	runtime: &Runtime,
+	width: u32,
+	height: u32,
+	pitch: i32,
+	format: PixelFormat,
+	component_type: ComponentType,
+	layout: ImageLayout,
+	memory_space: MemorySpace,
+	len: usize,
+	data: *mut u8
+) -> Result<sys::NvCVImage> {
+	if !matches!(memory_space, MemorySpace::CPU | MemorySpace::CPUPinned) {
+		return Err(Error::ImageNotInCPUMemory(memory_space));
+	}
+
+	let mut image = sys::NvCVImage::default();
+	unsafe { sys::NvCVImage_Init(runtime.nvcv_api(), &mut image, width, height, pitch, data.cast(), format, component_type, layout, memory_space) }?;
+	if image.pixel_bytes == 0 {
+		return Err(Error::InvalidImage("unknown pixel format or component type"));
+	}
+
+	let (rows, row_bytes) = if layout.is_interleaved() {
+		(height as usize, width as usize * image.pixel_bytes as usize)
+	} else if layout == ImageLayout::Planar {
+		(height as usize * image.num_components as usize, width as usize * image.component_bytes as usize)
+	} else {
+		return Err(Error::InvalidImage("YUV planar layouts are not supported"));
+	};
+	if rows == 0 || row_bytes == 0 {
+		return Ok(image);
+	}
+
+	let pitch = pitch.unsigned_abs() as usize;
+	if rows > 1 && pitch < row_bytes {
+		return Err(Error::InvalidImage("pitch is smaller than a row"));
 	}
+	if len < (rows - 1) * pitch + row_bytes {
+		return Err(Error::InvalidImage("buffer is too small for the image"));
+	}
+	if image.pitch < 0 {
+		// The SDK expects `pixels` to point to the top row, which is the last in memory.
+		image.pixels = unsafe { data.add((rows - 1) * pitch) }.cast();
+	}
+	Ok(image)
 }
 
 impl<'i> Deref for ImageView<'i> {
@@ -284,3 +395,99 @@ impl<'i> Deref for ImageView<'i> {
 		&self.image
 	}
 }
+
+/// A read-only view over borrowed pixels, e.g. a decoded frame shared behind an `Arc<[u8]>`.
+///
+/// An `ImageRef` dereferences to [`Image`], so it can be passed to a feature's `run` method or used as the source of
+/// [`Image::transfer_to`]. Since it never hands out `&mut Image`, it can't be bound as an SDK output or written to.
+pub struct ImageRef<'i> {
+	image: Image,
+	_phantom: PhantomData<&'i [u8]>
+}
+
+impl<'i> ImageRef<'i> {
+	/// Creates a view over tightly packed, interleaved 8-bit pixels in CPU memory. See [`ImageView::new_rgb`].
+	pub fn new_rgb(width: u32, height: u32, format: PixelFormat, data: &'i [u8]) -> Result<ImageRef<'i>> {
+		ImageRef::new_rgb_in(Runtime::global()?, width, height, format, data)
+	}
+
+	pub fn new_rgb_in(runtime: &Arc<Runtime>, width: u32, height: u32, format: PixelFormat, data: &'i [u8]) -> Result<ImageRef<'i>> {
+		ImageRef::from_raw_parts_in(
+			runtime,
+			width,
+			height,
+			packed_pitch(width, format)?,
+			format,
+			ComponentType::U8,
+			ImageLayout::Interleaved,
+			MemorySpace::CPU,
+			data
+		)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn from_raw_parts(
+		width: u32,
+		height: u32,
+		pitch: i32,
+		format: PixelFormat,
+		component_type: ComponentType,
+		layout: ImageLayout,
+		memory_space: MemorySpace,
+		data: &'i [u8]
+	) -> Result<ImageRef<'i>> {
+		ImageRef::from_raw_parts_in(Runtime::global()?, width, height, pitch, format, component_type, layout, memory_space, data)
+	}
+
+	/// Creates a read-only view over the pixels in `data`. See [`ImageView::from_raw_parts_in`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn from_raw_parts_in(
+		runtime: &Arc<Runtime>,
+		width: u32,
+		height: u32,
+		pitch: i32,
+		format: PixelFormat,
+		component_type: ComponentType,
+		layout: ImageLayout,
+		memory_space: MemorySpace,
+		data: &'i [u8]
+	) -> Result<ImageRef<'i>> {
+		// The SDK only reads from `data`; `ImageRef` never exposes the image mutably.
+		let image = init_view(runtime, width, height, pitch, format, component_type, layout, memory_space, data.len(), data.as_ptr().cast_mut())?;
+		Ok(ImageRef {
+			image: Image {
+				inner: Box::new(image),
+				runtime: Arc::clone(runtime),
+				drop: false
+			},
+			_phantom: PhantomData
+		})
+	}
+}
+
+impl<'i> Deref for ImageRef<'i> {
+	type Target = Image;
+
+	fn deref(&self) -> &Self::Target {
+		&self.image
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{PixelFormat, packed_pitch};
+
+	#[test]
+	fn packed_pitch_by_format() {
+		assert_eq!(packed_pitch(10, PixelFormat::Y).unwrap(), 10);
+		assert_eq!(packed_pitch(10, PixelFormat::RGB).unwrap(), 30);
+		assert_eq!(packed_pitch(10, PixelFormat::BGRA).unwrap(), 40);
+	}
+
+	#[test]
+	fn packed_pitch_rejects_invalid() {
+		assert!(packed_pitch(10, PixelFormat::YUV420).is_err());
+		assert!(packed_pitch(10, PixelFormat::Unknown).is_err());
+		assert!(packed_pitch(u32::MAX, PixelFormat::RGBA).is_err());
+	}
+}
@@ -0,0 +1,260 @@
+use std::{mem, ptr::NonNull, slice};
+
+use super::{ComponentType, Image, MemorySpace};
+use crate::{Error, Result};
+
+/// A primitive type that the components of an image can be accessed as.
+///
+/// # Safety
+/// `TYPE` must be the component type with the same size & representation as `Self`, and every bit pattern must be a
+/// valid `Self`.
+pub unsafe trait Component: Copy + 'static {
+	const TYPE: ComponentType;
+}
+
+macro_rules! impl_component {
+	($($ty:ty => $variant:ident),*) => {
+		$(unsafe impl Component for $ty {
+			const TYPE: ComponentType = ComponentType::$variant;
+		})*
+	};
+}
+
+impl_component!(u8 => U8, u16 => U16, i16 => S16, u32 => U32, i32 => S32, f32 => F32, u64 => U64, i64 => S64, f64 => F64);
+
+/// Where each row lives in an image's pixel buffer, in units of bytes or components.
+#[derive(Clone, Copy)]
+struct Rows {
+	height: usize,
+	/// The length of a row, excluding padding.
+	len: usize,
+	/// The distance between the start of consecutive rows.
+	pitch: usize,
+	/// Rows are stored bottom-up, i.e. the image has a negative pitch.
+	bottom_up: bool
+}
+
+impl Rows {
+	#[inline]
+	fn offset(&self, y: usize) -> usize {
+		if self.bottom_up { (self.height - 1 - y) * self.pitch } else { y * self.pitch }
+	}
+
+	#[inline]
+	fn in_units_of(self, size: usize) -> Rows {
+		Rows {
+			len: self.len / size,
+			pitch: self.pitch / size,
+			..self
+		}
+	}
+}
+
+impl Image {
+	/// Validates that the pixels can be accessed by the CPU, returning the start & length of the buffer spanning all
+	/// rows.
+	fn cpu_span(&self) -> Result<(*mut u8, usize, Rows)> {
+		let inner = self.ref_inner();
+		match inner.gpu_mem {
+			MemorySpace::CPU | MemorySpace::CPUPinned => {}
+			memory_space => return Err(Error::ImageNotInCPUMemory(memory_space))
+		}
+		if !inner.planar.is_interleaved() {
+			return Err(Error::InvalidImage("planar images are not supported"));
+		}
+
+		let rows = Rows {
+			height: inner.height as usize,
+			len: inner.width as usize * inner.pixel_bytes as usize,
+			pitch: inner.pitch.unsigned_abs() as usize,
+			bottom_up: inner.pitch < 0
+		};
+		if rows.height == 0 || rows.len == 0 {
+			return Ok((NonNull::dangling().as_ptr(), 0, rows));
+		}
+		if rows.height > 1 && rows.pitch < rows.len {
+			return Err(Error::InvalidImage("pitch is smaller than a row"));
+		}
+		let Some(pixels) = self.pixels_ptr() else {
+			return Err(Error::InvalidImage("image has no pixel buffer"));
+		};
+
+		let len = (rows.height - 1) * rows.pitch + rows.len;
+		let mut start = pixels.as_ptr().cast::<u8>();
+		if rows.bottom_up {
+			// `pixels` points to the top row, which is the last in memory.
+			start = unsafe { start.sub((rows.height - 1) * rows.pitch) };
+		}
+		Ok((start, len, rows))
+	}
+
+	fn cpu_component_span<T: Component>(&self) -> Result<(*mut T, usize, Rows)> {
+		let found = self.component_type();
+		if found != T::TYPE {
+			return Err(Error::ComponentTypeMismatch { expected: T::TYPE, found });
+		}
+
+		let (start, len, rows) = self.cpu_span()?;
+		let size = mem::size_of::<T>();
+		if len == 0 {
+			return Ok((NonNull::dangling().as_ptr(), 0, rows.in_units_of(size)));
+		}
+		if start.align_offset(mem::align_of::<T>()) != 0 || rows.pitch % size != 0 || rows.len % size != 0 {
+			return Err(Error::InvalidImage("pixel buffer is not aligned to its component type"));
+		}
+		Ok((start.cast(), len / size, rows.in_units_of(size)))
+	}
+
+	fn cpu_components<T: Component>(&self) -> Result<(&[T], Rows)> {
+		let (start, len, rows) = self.cpu_component_span()?;
+		Ok((unsafe { slice::from_raw_parts(start, len) }, rows))
+	}
+
+	fn cpu_components_mut<T: Component>(&mut self) -> Result<(&mut [T], Rows)> {
+		let (start, len, rows) = self.cpu_component_span()?;
+		Ok((unsafe { slice::from_raw_parts_mut(start, len) }, rows))
+	}
+
+	/// Returns the raw bytes of a CPU or pinned image, from the start of its first row in memory to the end of its
+	/// last, including any padding between rows. If the image has a negative [stride](Image::stride), the rows are
+	/// stored bottom-up.
+	///
+	/// Returns an error if the image lives in GPU memory or has a planar layout.
+	pub fn pixels(&self) -> Result<&[u8]> {
+		let (start, len, _) = self.cpu_span()?;
+		Ok(unsafe { slice::from_raw_parts(start, len) })
+	}
+
+	/// The mutable counterpart to [`Image::pixels`].
+	pub fn pixels_mut(&mut self) -> Result<&mut [u8]> {
+		let (start, len, _) = self.cpu_span()?;
+		Ok(unsafe { slice::from_raw_parts_mut(start, len) })
+	}
+
+	/// Like [`Image::pixels`], but returns the buffer as components of type `T`, which must match the image's
+	/// [component type](Image::component_type).
+	pub fn as_slice<T: Component>(&self) -> Result<&[T]> {
+		self.cpu_components().map(|(components, _)| components)
+	}
+
+	/// Returns the components of row `y`, counting from the top of the image, or `None` if `y` is out of bounds.
+	pub fn row<T: Component>(&self, y: u32) -> Result<Option<&[T]>> {
+		let (components, rows) = self.cpu_components()?;
+		let y = y as usize;
+		Ok((y < rows.height).then(|| &components[rows.offset(y)..][..rows.len]))
+	}
+
+	/// Returns an iterator over the components of each row, from top to bottom.
+	pub fn rows<T: Component>(&self) -> Result<impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_> {
+		let (components, rows) = self.cpu_components()?;
+		Ok((0..rows.height).map(move |y| &components[rows.offset(y)..][..rows.len]))
+	}
+
+	/// The mutable counterpart to [`Image::rows`].
+	pub fn rows_mut<T: Component>(&mut self) -> Result<impl DoubleEndedIterator<Item = &mut [T]> + ExactSizeIterator + '_> {
+		let (components, rows) = self.cpu_components_mut()?;
+		Ok(split_rows(components, rows).into_iter())
+	}
+
+	/// Returns the components of the pixel at `(x, y)`, or `None` if it is out of bounds.
+	pub fn get_pixel<T: Component>(&self, x: u32, y: u32) -> Result<Option<&[T]>> {
+		let pixel_len = self.pixel_bytes() / mem::size_of::<T>();
+		Ok(self
+			.row(y)?
+			.filter(|_| x < self.width())
+			.map(|row| &row[x as usize * pixel_len..][..pixel_len]))
+	}
+
+	/// Sets every pixel to `color`, which must have one value per component, e.g. `&[0u8, 255, 0]` for green in an
+	/// RGB image.
+	pub fn fill<T: Component>(&mut self, color: &[T]) -> Result<()> {
+		let pixel_len = self.pixel_bytes() / mem::size_of::<T>();
+		if color.len() != pixel_len {
+			return Err(Error::InvalidImage("fill color does not have one value per component"));
+		}
+		for row in self.rows_mut::<T>()? {
+			for pixel in row.chunks_exact_mut(pixel_len) {
+				pixel.copy_from_slice(color);
+			}
+		}
+		Ok(())
+	}
+
+	/// Copies pixel data into the image from `src`, whose rows start every `src_stride` bytes. Rows are copied top to
+	/// bottom, with no conversion; `src` must have the same pixel format & component type as the image.
+	pub fn copy_from_slice(&mut self, src: &[u8], src_stride: usize) -> Result<()> {
+		let (start, len, rows) = self.cpu_span()?;
+		if rows.height == 0 || rows.len == 0 {
+			return Ok(());
+		}
+		if src_stride < rows.len || src.len() < (rows.height - 1) * src_stride + rows.len {
+			return Err(Error::InvalidImage("source buffer is too small for the image"));
+		}
+		let dst = unsafe { slice::from_raw_parts_mut(start, len) };
+		for (dst_row, src_row) in split_rows(dst, rows).into_iter().zip(src.chunks(src_stride)) {
+			dst_row.copy_from_slice(&src_row[..rows.len]);
+		}
+		Ok(())
+	}
+}
+
+/// Splits a pixel buffer into its rows, from top to bottom.
+fn split_rows<T>(buffer: &mut [T], rows: Rows) -> Vec<&mut [T]> {
+	if buffer.is_empty() {
+		return Vec::new();
+	}
+	let mut split: Vec<&mut [T]> = buffer.chunks_mut(rows.pitch.max(rows.len)).map(|row| &mut row[..rows.len]).collect();
+	if rows.bottom_up {
+		split.reverse();
+	}
+	split
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Rows, split_rows};
+
+	const TOP_DOWN: Rows = Rows {
+		height: 3,
+		len: 3,
+		pitch: 4,
+		bottom_up: false
+	};
+	const BOTTOM_UP: Rows = Rows { bottom_up: true, ..TOP_DOWN };
+
+	#[test]
+	fn row_offsets() {
+		assert_eq!((0..3).map(|y| TOP_DOWN.offset(y)).collect::<Vec<_>>(), [0, 4, 8]);
+		assert_eq!((0..3).map(|y| BOTTOM_UP.offset(y)).collect::<Vec<_>>(), [8, 4, 0]);
+	}
+
+	#[test]
+	fn rows_in_components() {
+		let rows = Rows { len: 12, pitch: 16, ..TOP_DOWN }.in_units_of(4);
+		assert_eq!((rows.len, rows.pitch), (3, 4));
+		assert_eq!(rows.offset(2), 8);
+	}
+
+	#[test]
+	fn split_rows_skips_padding() {
+		let mut buffer = [0, 1, 2, 99, 10, 11, 12, 99, 20, 21, 22];
+		let rows = split_rows(&mut buffer, TOP_DOWN);
+		assert_eq!(rows, [&[0, 1, 2], &[10, 11, 12], &[20, 21, 22]]);
+	}
+
+	#[test]
+	fn split_rows_bottom_up() {
+		// With a negative pitch, the top row is the last one in memory.
+		let mut buffer = [20, 21, 22, 99, 10, 11, 12, 99, 0, 1, 2];
+		let rows = split_rows(&mut buffer, BOTTOM_UP);
+		assert_eq!(rows, [&[0, 1, 2], &[10, 11, 12], &[20, 21, 22]]);
+	}
+
+	#[test]
+	fn split_single_row() {
+		let mut buffer = [0, 1, 2];
+		let rows = split_rows(&mut buffer, Rows { height: 1, pitch: 0, ..TOP_DOWN });
+		assert_eq!(rows, [&[0, 1, 2]]);
+		assert!(split_rows::<u8>(&mut [], TOP_DOWN).is_empty());
+	}
+}
@@ -5,12 +5,14 @@
     clippy::missing_safety_doc
 )]
 
-use std::{ffi::c_void, ptr};
+use std::{
+    ffi::{c_char, c_void},
+    ptr,
+};
 
 use bitflags::bitflags;
-use libloading::Library;
 
-use crate::ar::sys::CUstream;
+use crate::{ar::sys::CUstream, macros::define_api};
 
 #[repr(u32)]
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,6 +86,7 @@ impl NvCVImage_Layout {
 }
 
 bitflags! {
+    #[repr(transparent)]
     #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
     pub struct NvCVImage_ColorSpace: u8 {
         const R601 = 0x00;
@@ -171,8 +174,167 @@ pub struct NvCVPoint2i {
     pub y: i32,
 }
 
+define_api! {
+    pub struct NvCVImage_Api {
+        fn NvCVImage_Init(
+            *mut NvCVImage,
+            u32,
+            u32,
+            i32,
+            *mut c_void,
+            NvCVImage_PixelFormat,
+            NvCVImage_ComponentType,
+            NvCVImage_Layout,
+            NvCVImage_MemorySpace,
+        ) -> i32;
+        fn NvCVImage_InitView(*mut NvCVImage, *mut NvCVImage, i32, i32, u32, u32);
+        fn NvCVImage_Alloc(
+            *mut NvCVImage,
+            u32,
+            u32,
+            NvCVImage_PixelFormat,
+            NvCVImage_ComponentType,
+            u32,
+            u32,
+            u32,
+        ) -> i32;
+        fn NvCVImage_Realloc(
+            *mut NvCVImage,
+            u32,
+            u32,
+            NvCVImage_PixelFormat,
+            NvCVImage_ComponentType,
+            NvCVImage_Layout,
+            NvCVImage_MemorySpace,
+            u32,
+        ) -> i32;
+        fn NvCVImage_Dealloc(*mut NvCVImage);
+        fn NvCVImage_Create(
+            u32,
+            u32,
+            NvCVImage_PixelFormat,
+            NvCVImage_ComponentType,
+            NvCVImage_Layout,
+            NvCVImage_MemorySpace,
+            u32,
+            *mut *mut NvCVImage,
+        ) -> i32;
+        fn NvCVImage_Destroy(*mut NvCVImage);
+        fn NvCVImage_ComponentOffsets(
+            NvCVImage_PixelFormat,
+            *mut i32,
+            *mut i32,
+            *mut i32,
+            *mut i32,
+            *mut i32,
+        );
+        fn NvCVImage_Transfer(
+            *const NvCVImage,
+            *mut NvCVImage,
+            f32,
+            CUstream,
+            *mut NvCVImage,
+        ) -> i32;
+        fn NvCVImage_TransferRect(
+            *const NvCVImage,
+            *const NvCVRect2i,
+            *mut NvCVImage,
+            *const NvCVPoint2i,
+            f32,
+            CUstream,
+            *mut NvCVImage,
+        ) -> i32;
+
+        optional {
+            fn NvCV_GetErrorStringFromCode(i32) -> *const c_char;
+            fn NvCVImage_DeallocAsync(*mut NvCVImage, CUstream);
+            fn NvCVImage_TransferFromYUV(
+                *const c_void,
+                i32,
+                i32,
+                *const c_void,
+                *const c_void,
+                i32,
+                i32,
+                NvCVImage_PixelFormat,
+                NvCVImage_ComponentType,
+                NvCVImage_ColorSpace,
+                NvCVImage_MemorySpace,
+                *mut NvCVImage,
+                *const NvCVRect2i,
+                f32,
+                CUstream,
+                *mut NvCVImage,
+            ) -> i32;
+            fn NvCVImage_TransferToYUV(
+                *const NvCVImage,
+                *const NvCVRect2i,
+                *const c_void,
+                i32,
+                i32,
+                *const c_void,
+                *const c_void,
+                i32,
+                i32,
+                NvCVImage_PixelFormat,
+                NvCVImage_ComponentType,
+                NvCVImage_ColorSpace,
+                NvCVImage_MemorySpace,
+                f32,
+                CUstream,
+                *mut NvCVImage,
+            ) -> i32;
+            fn NvCVImage_MapResource(*mut NvCVImage, CUstream) -> i32;
+            fn NvCVImage_UnmapResource(*mut NvCVImage, CUstream) -> i32;
+            fn NvCVImage_Composite(
+                *const NvCVImage,
+                *const NvCVImage,
+                *const NvCVImage,
+                *mut NvCVImage,
+                CUstream,
+            ) -> i32;
+            fn NvCVImage_CompositeRect(
+                *const NvCVImage,
+                *const NvCVPoint2i,
+                *const NvCVImage,
+                *const NvCVPoint2i,
+                *const NvCVImage,
+                u32,
+                *mut NvCVImage,
+                *const NvCVPoint2i,
+                CUstream,
+            ) -> i32;
+            fn NvCVImage_CompositeOverConstant(
+                *const NvCVImage,
+                *const NvCVImage,
+                *const c_void,
+                *mut NvCVImage,
+                CUstream,
+            ) -> i32;
+            fn NvCVImage_FlipY(*const NvCVImage, *mut NvCVImage) -> i32;
+            fn NvCVImage_GetYUVPointers(
+                *mut NvCVImage,
+                *mut *mut u8,
+                *mut *mut u8,
+                *mut *mut u8,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+                *mut i32,
+            ) -> i32;
+            fn NvCVImage_Sharpen(
+                f32,
+                *const NvCVImage,
+                *mut NvCVImage,
+                CUstream,
+                *mut NvCVImage,
+            ) -> i32;
+        }
+    }
+}
+
 pub unsafe fn NvCVImage_Init(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     width: u32,
     height: u32,
@@ -183,24 +345,17 @@ pub unsafe fn NvCVImage_Init(
     layout: NvCVImage_Layout,
     mem_space: NvCVImage_MemorySpace,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *mut NvCVImage,
-        u32,
-        u32,
-        i32,
-        *mut c_void,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        NvCVImage_Layout,
-        NvCVImage_MemorySpace,
-    ) -> i32>(b"NvCVImage_Init")?;
-    crate::error::to_status(sym(
-        im, width, height, pitch, pixels, format, r#type, layout, mem_space,
-    ))
+    let sym = api.NvCVImage_Init()?;
+    crate::error::to_status(
+        "NvCVImage_Init",
+        sym(
+            im, width, height, pitch, pixels, format, r#type, layout, mem_space,
+        ),
+    )
 }
 
 pub unsafe fn NvCVImage_InitView(
-    library: &Library,
+    api: &NvCVImage_Api,
     subImg: *mut NvCVImage,
     fullImg: *mut NvCVImage,
     x: i32,
@@ -208,16 +363,13 @@ pub unsafe fn NvCVImage_InitView(
     width: u32,
     height: u32,
 ) -> crate::Result<()> {
-    let sym = library
-        .get::<unsafe extern "C" fn(*mut NvCVImage, *mut NvCVImage, i32, i32, u32, u32)>(
-            b"NvCVImage_InitView",
-        )?;
+    let sym = api.NvCVImage_InitView()?;
     sym(subImg, fullImg, x, y, width, height);
     Ok(())
 }
 
 pub unsafe fn NvCVImage_Alloc(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     width: u32,
     height: u32,
@@ -227,30 +379,24 @@ pub unsafe fn NvCVImage_Alloc(
     mem_space: NvCVImage_MemorySpace,
     alignment: u32,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *mut NvCVImage,
-        u32,
-        u32,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        u32,
-        u32,
-        u32,
-    ) -> i32>(b"NvCVImage_Alloc")?;
-    crate::error::to_status(sym(
-        im,
-        width,
-        height,
-        format,
-        r#type,
-        layout as u8 as u32,
-        mem_space as u8 as u32,
-        alignment,
-    ))
+    let sym = api.NvCVImage_Alloc()?;
+    crate::error::to_status(
+        "NvCVImage_Alloc",
+        sym(
+            im,
+            width,
+            height,
+            format,
+            r#type,
+            layout as u8 as u32,
+            mem_space as u8 as u32,
+            alignment,
+        ),
+    )
 }
 
 pub unsafe fn NvCVImage_Realloc(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     width: u32,
     height: u32,
@@ -260,40 +406,33 @@ pub unsafe fn NvCVImage_Realloc(
     mem_space: NvCVImage_MemorySpace,
     alignment: u32,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *mut NvCVImage,
-        u32,
-        u32,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        NvCVImage_Layout,
-        NvCVImage_MemorySpace,
-        u32,
-    ) -> i32>(b"NvCVImage_Realloc")?;
-    crate::error::to_status(sym(
-        im, width, height, format, r#type, layout, mem_space, alignment,
-    ))
-}
-
-pub unsafe fn NvCVImage_Dealloc(library: &Library, im: *mut NvCVImage) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(*mut NvCVImage) -> ()>(b"NvCVImage_Dealloc")?;
+    let sym = api.NvCVImage_Realloc()?;
+    crate::error::to_status(
+        "NvCVImage_Realloc",
+        sym(
+            im, width, height, format, r#type, layout, mem_space, alignment,
+        ),
+    )
+}
+
+pub unsafe fn NvCVImage_Dealloc(api: &NvCVImage_Api, im: *mut NvCVImage) -> crate::Result<()> {
+    let sym = api.NvCVImage_Dealloc()?;
     sym(im);
     Ok(())
 }
 
 pub unsafe fn NvCVImage_DeallocAsync(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library
-        .get::<unsafe extern "C" fn(*mut NvCVImage, CUstream) -> ()>(b"NvCVImage_DeallocAsync")?;
+    let sym = api.NvCVImage_DeallocAsync()?;
     sym(im, stream);
     Ok(())
 }
 
 pub unsafe fn NvCVImage_Create(
-    library: &Library,
+    api: &NvCVImage_Api,
     width: u32,
     height: u32,
     format: NvCVImage_PixelFormat,
@@ -302,33 +441,27 @@ pub unsafe fn NvCVImage_Create(
     mem_space: NvCVImage_MemorySpace,
     alignment: u32,
 ) -> crate::Result<*mut NvCVImage> {
-    let sym = library.get::<unsafe extern "C" fn(
-        u32,
-        u32,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        NvCVImage_Layout,
-        NvCVImage_MemorySpace,
-        u32,
-        *mut *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_Create")?;
+    let sym = api.NvCVImage_Create()?;
     let mut output: *mut NvCVImage = ptr::null_mut();
-    crate::error::to_status(sym(
-        width,
-        height,
-        format,
-        r#type,
-        layout,
-        mem_space,
-        alignment,
-        &mut output as *mut _,
-    ))?;
+    crate::error::to_status(
+        "NvCVImage_Create",
+        sym(
+            width,
+            height,
+            format,
+            r#type,
+            layout,
+            mem_space,
+            alignment,
+            &mut output as *mut _,
+        ),
+    )?;
     assert!(!output.is_null());
     Ok(output)
 }
 
-pub unsafe fn NvCVImage_Destroy(library: &Library, im: *mut NvCVImage) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(*mut NvCVImage)>(b"NvCVImage_Destroy")?;
+pub unsafe fn NvCVImage_Destroy(api: &NvCVImage_Api, im: *mut NvCVImage) -> crate::Result<()> {
+    let sym = api.NvCVImage_Destroy()?;
     sym(im);
     Ok(())
 }
@@ -343,17 +476,10 @@ pub struct NvCVImage_ComponentOffsets {
 }
 
 pub unsafe fn NvCVImage_ComponentOffsets(
-    library: &Library,
+    api: &NvCVImage_Api,
     format: NvCVImage_PixelFormat,
 ) -> crate::Result<NvCVImage_ComponentOffsets> {
-    let sym = library.get::<unsafe extern "C" fn(
-        NvCVImage_PixelFormat,
-        *mut i32,
-        *mut i32,
-        *mut i32,
-        *mut i32,
-        *mut i32,
-    )>(b"NvCVImage_ComponentOffsets")?;
+    let sym = api.NvCVImage_ComponentOffsets()?;
     let mut out = NvCVImage_ComponentOffsets::default();
     sym(
         format, &mut out.r, &mut out.g, &mut out.b, &mut out.a, &mut out.y,
@@ -362,25 +488,19 @@ pub unsafe fn NvCVImage_ComponentOffsets(
 }
 
 pub unsafe fn NvCVImage_Transfer(
-    library: &Library,
+    api: &NvCVImage_Api,
     src: *const NvCVImage,
     dst: *mut NvCVImage,
     scale: f32,
     stream: CUstream,
     tmp: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *mut NvCVImage,
-        f32,
-        CUstream,
-        *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_Transfer")?;
-    crate::error::to_status(sym(src, dst, scale, stream, tmp))
+    let sym = api.NvCVImage_Transfer()?;
+    crate::error::to_status("NvCVImage_Transfer", sym(src, dst, scale, stream, tmp))
 }
 
 pub unsafe fn NvCVImage_TransferRect(
-    library: &Library,
+    api: &NvCVImage_Api,
     src: *const NvCVImage,
     src_rect: *const NvCVRect2i,
     dst: *mut NvCVImage,
@@ -389,20 +509,15 @@ pub unsafe fn NvCVImage_TransferRect(
     stream: CUstream,
     tmp: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *const NvCVRect2i,
-        *mut NvCVImage,
-        *const NvCVPoint2i,
-        f32,
-        CUstream,
-        *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_TransferRect")?;
-    crate::error::to_status(sym(src, src_rect, dst, dst_pt, scale, stream, tmp))
+    let sym = api.NvCVImage_TransferRect()?;
+    crate::error::to_status(
+        "NvCVImage_TransferRect",
+        sym(src, src_rect, dst, dst_pt, scale, stream, tmp),
+    )
 }
 
 pub unsafe fn NvCVImage_TransferFromYUV(
-    library: &Library,
+    api: &NvCVImage_Api,
     y: *const c_void,
     y_pix_bytes: i32,
     y_pitch: i32,
@@ -420,46 +535,32 @@ pub unsafe fn NvCVImage_TransferFromYUV(
     stream: CUstream,
     tmp: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const c_void,
-        i32,
-        i32,
-        *const c_void,
-        *const c_void,
-        i32,
-        i32,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        NvCVImage_ColorSpace,
-        NvCVImage_MemorySpace,
-        *mut NvCVImage,
-        *const NvCVRect2i,
-        f32,
-        CUstream,
-        *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_TransferFromYUV")?;
-    crate::error::to_status(sym(
-        y,
-        y_pix_bytes,
-        y_pitch,
-        u,
-        v,
-        uv_pix_bytes,
-        uv_pitch,
-        yuv_format,
-        yuv_type,
-        yuv_color_space,
-        yuv_mem_space,
-        dst,
-        dst_rect,
-        scale,
-        stream,
-        tmp,
-    ))
+    let sym = api.NvCVImage_TransferFromYUV()?;
+    crate::error::to_status(
+        "NvCVImage_TransferFromYUV",
+        sym(
+            y,
+            y_pix_bytes,
+            y_pitch,
+            u,
+            v,
+            uv_pix_bytes,
+            uv_pitch,
+            yuv_format,
+            yuv_type,
+            yuv_color_space,
+            yuv_mem_space,
+            dst,
+            dst_rect,
+            scale,
+            stream,
+            tmp,
+        ),
+    )
 }
 
 pub unsafe fn NvCVImage_TransferToYUV(
-    library: &Library,
+    api: &NvCVImage_Api,
     src: *const NvCVImage,
     src_rect: *const NvCVRect2i,
     y: *const c_void,
@@ -477,84 +578,62 @@ pub unsafe fn NvCVImage_TransferToYUV(
     stream: CUstream,
     tmp: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *const NvCVRect2i,
-        *const c_void,
-        i32,
-        i32,
-        *const c_void,
-        *const c_void,
-        i32,
-        i32,
-        NvCVImage_PixelFormat,
-        NvCVImage_ComponentType,
-        NvCVImage_ColorSpace,
-        NvCVImage_MemorySpace,
-        f32,
-        CUstream,
-        *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_TransferToYUV")?;
-    crate::error::to_status(sym(
-        src,
-        src_rect,
-        y,
-        y_pix_bytes,
-        y_pitch,
-        u,
-        v,
-        uv_pix_bytes,
-        uv_pitch,
-        yuv_format,
-        yuv_type,
-        yuv_color_space,
-        yuv_mem_space,
-        scale,
-        stream,
-        tmp,
-    ))
+    let sym = api.NvCVImage_TransferToYUV()?;
+    crate::error::to_status(
+        "NvCVImage_TransferToYUV",
+        sym(
+            src,
+            src_rect,
+            y,
+            y_pix_bytes,
+            y_pitch,
+            u,
+            v,
+            uv_pix_bytes,
+            uv_pitch,
+            yuv_format,
+            yuv_type,
+            yuv_color_space,
+            yuv_mem_space,
+            scale,
+            stream,
+            tmp,
+        ),
+    )
 }
 
 pub unsafe fn NvCVImage_MapResource(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library
-        .get::<unsafe extern "C" fn(*mut NvCVImage, CUstream) -> i32>(b"NvCVImage_MapResource")?;
-    crate::error::to_status(sym(im, stream))
+    let sym = api.NvCVImage_MapResource()?;
+    crate::error::to_status("NvCVImage_MapResource", sym(im, stream))
 }
 
 pub unsafe fn NvCVImage_UnmapResource(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library
-        .get::<unsafe extern "C" fn(*mut NvCVImage, CUstream) -> i32>(b"NvCVImage_UnmapResource")?;
-    crate::error::to_status(sym(im, stream))
+    let sym = api.NvCVImage_UnmapResource()?;
+    crate::error::to_status("NvCVImage_UnmapResource", sym(im, stream))
 }
 
 pub unsafe fn NvCVImage_Composite(
-    library: &Library,
+    api: &NvCVImage_Api,
     fg: *const NvCVImage,
     bg: *const NvCVImage,
     mat: *const NvCVImage,
     dst: *mut NvCVImage,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *const NvCVImage,
-        *const NvCVImage,
-        *mut NvCVImage,
-        CUstream,
-    ) -> i32>(b"NvCVImage_Composite")?;
-    crate::error::to_status(sym(fg, bg, mat, dst, stream))
+    let sym = api.NvCVImage_Composite()?;
+    crate::error::to_status("NvCVImage_Composite", sym(fg, bg, mat, dst, stream))
 }
 
 pub unsafe fn NvCVImage_CompositeRect(
-    library: &Library,
+    api: &NvCVImage_Api,
     fg: *const NvCVImage,
     fg_org: *const NvCVPoint2i,
     bg: *const NvCVImage,
@@ -565,56 +644,45 @@ pub unsafe fn NvCVImage_CompositeRect(
     dst_org: *const NvCVPoint2i,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *const NvCVPoint2i,
-        *const NvCVImage,
-        *const NvCVPoint2i,
-        *const NvCVImage,
-        u32,
-        *mut NvCVImage,
-        *const NvCVPoint2i,
-        CUstream,
-    ) -> i32>(b"NvCVImage_CompositeRect")?;
-    crate::error::to_status(sym(
-        fg,
-        fg_org,
-        bg,
-        bg_org,
-        mat,
-        premultiplied.into(),
-        dst,
-        dst_org,
-        stream,
-    ))
+    let sym = api.NvCVImage_CompositeRect()?;
+    crate::error::to_status(
+        "NvCVImage_CompositeRect",
+        sym(
+            fg,
+            fg_org,
+            bg,
+            bg_org,
+            mat,
+            premultiplied.into(),
+            dst,
+            dst_org,
+            stream,
+        ),
+    )
 }
 
 pub unsafe fn NvCVImage_CompositeOverConstant(
-    library: &Library,
+    api: &NvCVImage_Api,
     src: *const NvCVImage,
     mat: *const NvCVImage,
     bg_color: *const c_void,
     dst: *mut NvCVImage,
     stream: CUstream,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *const NvCVImage,
-        *const NvCVImage,
-        *const c_void,
-        *mut NvCVImage,
-        CUstream,
-    ) -> i32>(b"NvCVImage_CompositeOverConstant")?;
-    crate::error::to_status(sym(src, mat, bg_color, dst, stream))
+    let sym = api.NvCVImage_CompositeOverConstant()?;
+    crate::error::to_status(
+        "NvCVImage_CompositeOverConstant",
+        sym(src, mat, bg_color, dst, stream),
+    )
 }
 
 pub unsafe fn NvCVImage_FlipY(
-    library: &Library,
+    api: &NvCVImage_Api,
     src: *const NvCVImage,
     dst: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library
-        .get::<unsafe extern "C" fn(*const NvCVImage, *mut NvCVImage) -> i32>(b"NvCVImage_FlipY")?;
-    crate::error::to_status(sym(src, dst))
+    let sym = api.NvCVImage_FlipY()?;
+    crate::error::to_status("NvCVImage_FlipY", sym(src, dst))
 }
 
 #[derive(Debug)]
@@ -628,19 +696,10 @@ pub struct NvCVImage_GetYUVPointers {
     pub c_row_bytes: i32,
 }
 pub unsafe fn NvCVImage_GetYUVPointers(
-    library: &Library,
+    api: &NvCVImage_Api,
     im: *mut NvCVImage,
 ) -> crate::Result<NvCVImage_GetYUVPointers> {
-    let sym = library.get::<unsafe extern "C" fn(
-        *mut NvCVImage,
-        *mut *mut u8,
-        *mut *mut u8,
-        *mut *mut u8,
-        *mut i32,
-        *mut i32,
-        *mut i32,
-        *mut i32,
-    ) -> i32>(b"NvCVImage_GetYUVPointers")?;
+    let sym = api.NvCVImage_GetYUVPointers()?;
     let mut out = NvCVImage_GetYUVPointers {
         y: ptr::null_mut(),
         u: ptr::null_mut(),
@@ -650,33 +709,30 @@ pub unsafe fn NvCVImage_GetYUVPointers(
         y_row_bytes: 0,
         c_row_bytes: 0,
     };
-    crate::error::to_status(sym(
-        im,
-        &mut out.y,
-        &mut out.u,
-        &mut out.v,
-        &mut out.y_pix_bytes,
-        &mut out.c_pix_bytes,
-        &mut out.y_row_bytes,
-        &mut out.c_row_bytes,
-    ))?;
+    crate::error::to_status(
+        "NvCVImage_GetYUVPointers",
+        sym(
+            im,
+            &mut out.y,
+            &mut out.u,
+            &mut out.v,
+            &mut out.y_pix_bytes,
+            &mut out.c_pix_bytes,
+            &mut out.y_row_bytes,
+            &mut out.c_row_bytes,
+        ),
+    )?;
     Ok(out)
 }
 
 pub unsafe fn NvCVImage_Sharpen(
-    library: &Library,
+    api: &NvCVImage_Api,
     sharpness: f32,
     src: *const NvCVImage,
     dst: *mut NvCVImage,
     stream: CUstream,
     tmp: *mut NvCVImage,
 ) -> crate::Result<()> {
-    let sym = library.get::<unsafe extern "C" fn(
-        f32,
-        *const NvCVImage,
-        *mut NvCVImage,
-        CUstream,
-        *mut NvCVImage,
-    ) -> i32>(b"NvCVImage_Sharpen")?;
-    crate::error::to_status(sym(sharpness, src, dst, stream, tmp))
+    let sym = api.NvCVImage_Sharpen()?;
+    crate::error::to_status("NvCVImage_Sharpen", sym(sharpness, src, dst, stream, tmp))
 }
@@ -1,7 +1,16 @@
-use std::fmt;
+use std::{
+	ffi::{CStr, NulError, c_char},
+	fmt,
+	path::PathBuf
+};
+
+use crate::{
+	Version,
+	cv::{ComponentType, MemorySpace}
+};
 
 macro_rules! define_error {
-    (
+	(
 		$(#[$attr:meta])*
 		$vis:vis enum $name:ident {
 			$(
@@ -13,16 +22,31 @@ macro_rules! define_error {
 		$(#[$attr])*
 		$vis enum $name {
 			$(
-				$variant
-			),*
+				$variant,
+			)*
+			/// A status code not known to this version of the crate.
+			Unknown(i32)
+		}
+
+		impl $name {
+			/// Returns the raw status code.
+			pub fn code(&self) -> i32 {
+				match self {
+					$(
+						Self::$variant => $id,
+					)*
+					Self::Unknown(code) => *code
+				}
+			}
 		}
 
 		impl fmt::Display for $name {
 			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 				match self {
 					$(
-						Self::$variant => f.write_str($m)
-					),*
+						Self::$variant => f.write_str($m),
+					)*
+					Self::Unknown(code) => write!(f, "An unknown error has occurred (status code {code}).")
 				}
 			}
 		}
@@ -33,7 +57,7 @@ macro_rules! define_error {
 			fn from(value: i32) -> $name {
 				match value {
 					$($id => $name::$variant,)*
-					_ => unimplemented!(concat!("Unknown value `{}` for ", stringify!($name)), value)
+					_ => $name::Unknown(value)
 				}
 			}
 		}
@@ -41,52 +65,284 @@ macro_rules! define_error {
 }
 
 define_error! {
-	#[derive(Debug, Clone)]
+	/// A status code returned by the nvCV & nvAR libraries (`NvCV_Status`).
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 	pub enum NvError {
 		#[error("An otherwise unspecified error has occurred.")]
 		General = -1,
 		#[error("The requested feature is not yet implemented.")]
 		Unimplemented = -2,
 		#[error("There is not enough memory for the requested operation.")]
-		OutOfMemory = -3
+		OutOfMemory = -3,
+		#[error("The requested effect is not supported.")]
+		Effect = -4,
+		#[error("The given parameter selector is not valid in this effect filter.")]
+		Selector = -5,
+		#[error("An image buffer has not been specified.")]
+		Buffer = -6,
+		#[error("An invalid parameter value has been supplied for this effect + selector.")]
+		Parameter = -7,
+		#[error("Some parameters are not appropriately matched.")]
+		Mismatch = -8,
+		#[error("The specified pixel format is not accommodated.")]
+		PixelFormat = -9,
+		#[error("Error while loading the TRT model.")]
+		Model = -10,
+		#[error("Error loading the dynamic library.")]
+		Library = -11,
+		#[error("The effect has not been properly initialized.")]
+		Initialization = -12,
+		#[error("The file could not be found.")]
+		File = -13,
+		#[error("The requested feature was not found.")]
+		FeatureNotFound = -14,
+		#[error("A required parameter was not set.")]
+		MissingInput = -15,
+		#[error("The specified image resolution is not supported.")]
+		Resolution = -16,
+		#[error("The GPU is not supported.")]
+		UnsupportedGPU = -17,
+		#[error("The current GPU is not the one selected.")]
+		WrongGPU = -18,
+		#[error("The currently installed graphics driver is not supported.")]
+		UnsupportedDriver = -19,
+		#[error("There is no model with dependencies that match this system.")]
+		ModelDependencies = -20,
+		#[error("There has been a parsing or syntax error while reading a file.")]
+		Parse = -21,
+		#[error("The specified model does not exist and has been substituted.")]
+		ModelSubstitution = -22,
+		#[error("An error occurred while reading a file.")]
+		Read = -23,
+		#[error("An error occurred while writing a file.")]
+		Write = -24,
+		#[error("The selected parameter is read-only.")]
+		ParamReadOnly = -25,
+		#[error("TensorRT enqueue failed.")]
+		TRTEnqueue = -26,
+		#[error("Unexpected TensorRT bindings.")]
+		TRTBindings = -27,
+		#[error("An error occurred while creating a TensorRT context.")]
+		TRTContext = -28,
+		#[error("There was a problem creating the inference engine.")]
+		TRTInfer = -29,
+		#[error("There was a problem deserializing the inference runtime engine.")]
+		TRTEngine = -30,
+		#[error("An error has occurred in the NPP library.")]
+		NPP = -31,
+		#[error("No suitable model exists for the specified parameter configuration.")]
+		Config = -32,
+		#[error("A supplied parameter or buffer is not large enough.")]
+		TooSmall = -33,
+		#[error("A supplied parameter is too big.")]
+		TooBig = -34,
+		#[error("A supplied parameter is not the expected size.")]
+		WrongSize = -35,
+		#[error("The specified object was not found.")]
+		ObjectNotFound = -36,
+		#[error("A mathematical singularity has been encountered.")]
+		Singular = -37,
+		#[error("Nothing was rendered in the specified region.")]
+		NothingRendered = -38,
+		#[error("An iteration did not converge satisfactorily.")]
+		Convergence = -39,
+		#[error("An OpenGL error has occurred.")]
+		OpenGL = -98,
+		#[error("A Direct3D error has occurred.")]
+		Direct3D = -99,
+		#[error("An unspecified CUDA error has occurred.")]
+		CUDABase = -100,
+		#[error("A CUDA parameter is not within the acceptable range.")]
+		CUDAValue = -101,
+		#[error("There is not enough CUDA memory for the requested operation.")]
+		CUDAMemory = -102,
+		#[error("A CUDA pitch is not within the acceptable range.")]
+		CUDAPitch = -112,
+		#[error("The CUDA driver and runtime could not be initialized.")]
+		CUDAInit = -127,
+		#[error("The CUDA kernel launch has failed.")]
+		CUDALaunch = -819,
+		#[error("No suitable kernel image is available for the device.")]
+		CUDAKernel = -309,
+		#[error("The installed NVIDIA CUDA driver is older than the CUDA runtime library.")]
+		CUDADriver = -135,
+		#[error("The CUDA operation is not supported on the current system or device.")]
+		CUDAUnsupported = -901,
+		#[error("CUDA tried to load or store on an invalid memory address.")]
+		CUDAIllegalAddress = -800,
+		#[error("An otherwise unspecified CUDA error has been reported.")]
+		CUDA = -1099
 	}
 }
 
 #[derive(Debug)]
 pub enum Error {
-	Nv(NvError),
-	Dylib(libloading::Error)
+	/// An SDK call returned a non-success status.
+	Nv {
+		status: NvError,
+		/// The name of the FFI entry point that failed, e.g. `NvAR_SetObject`.
+		function: &'static str,
+		/// For parameter getters & setters, the full `NvAR_Parameter_*` key that was being accessed.
+		parameter: Option<String>
+	},
+	LoadLibrary {
+		path: PathBuf,
+		source: libloading::Error
+	},
+	UnsupportedBySdk(&'static str),
+	/// The loaded SDK is older than the version required by the requested feature or option.
+	UnsupportedSdkVersion {
+		required: Version,
+		found: Version
+	},
+	/// A model directory does not exist, is not a directory, or is not valid UTF-8.
+	InvalidModelDir(PathBuf),
+	/// A feature method was called that the feature was not configured for when it was built.
+	FeatureMisconfigured(&'static str),
+	/// A builder option was given a value outside the range the SDK accepts for its parameter.
+	InvalidParameterValue {
+		/// The full `NvAR_Parameter_*` key the value was meant for.
+		parameter: &'static CStr,
+		reason: &'static str
+	},
+	/// A string passed to the SDK contains an interior nul byte.
+	InteriorNul(NulError),
+	/// An image's pixels were accessed from the CPU, but the image lives in GPU memory.
+	ImageNotInCPUMemory(MemorySpace),
+	/// An image's components were accessed as a different type than the image holds.
+	ComponentTypeMismatch {
+		expected: ComponentType,
+		found: ComponentType
+	},
+	/// An image's descriptor or buffer is not valid for the requested operation.
+	InvalidImage(&'static str)
 }
 
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
-			Self::Nv(e) => e.fmt(f),
-			Self::Dylib(e) => {
-				f.write_str("Error loading symbol from dynamic library: ")?;
-				e.fmt(f)
+			Self::Nv {
+				status,
+				function,
+				parameter: Some(parameter)
+			} => write!(f, "`{function}` failed for `{parameter}`: {status}"),
+			Self::Nv { status, function, parameter: None } => write!(f, "`{function}` failed: {status}"),
+			Self::LoadLibrary { path, source } => {
+				write!(f, "An error occurred while attempting to load the dynamic library at `{}`: {source}", path.display())
+			}
+			Self::UnsupportedBySdk(symbol) => write!(f, "`{symbol}` is unsupported by this SDK version (symbol not found)"),
+			Self::UnsupportedSdkVersion { required, found } => {
+				write!(f, "This feature requires SDK version {required} or newer, but version {found} is loaded")
 			}
+			Self::InvalidModelDir(path) => write!(f, "`{}` is not a valid model directory", path.display()),
+			Self::FeatureMisconfigured(reason) => write!(f, "Feature is not configured for this call: {reason}"),
+			Self::InvalidParameterValue { parameter, reason } => write!(f, "Invalid value for `{}`: {reason}", parameter.to_string_lossy()),
+			Self::InteriorNul(e) => write!(f, "String passed to the SDK contains a nul byte at position {}", e.nul_position()),
+			Self::ImageNotInCPUMemory(memory_space) => write!(f, "Image pixels are not accessible from the CPU (memory space {memory_space:?})"),
+			Self::ComponentTypeMismatch { expected, found } => write!(f, "Expected an image with {expected:?} components, found {found:?}"),
+			Self::InvalidImage(reason) => write!(f, "Invalid image: {reason}")
 		}
 	}
 }
 
-impl std::error::Error for Error {}
-
-impl From<NvError> for Error {
-	fn from(e: NvError) -> Self {
-		Self::Nv(e)
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Nv { status, .. } => Some(status),
+			Self::LoadLibrary { source, .. } => Some(source),
+			Self::InteriorNul(e) => Some(e),
+			Self::UnsupportedBySdk(_)
+			| Self::UnsupportedSdkVersion { .. }
+			| Self::InvalidModelDir(_)
+			| Self::FeatureMisconfigured(_)
+			| Self::InvalidParameterValue { .. }
+			| Self::ImageNotInCPUMemory(_)
+			| Self::ComponentTypeMismatch { .. }
+			| Self::InvalidImage(_) => None
+		}
 	}
 }
 
-impl From<libloading::Error> for Error {
-	fn from(e: libloading::Error) -> Self {
-		Self::Dylib(e)
+impl From<NulError> for Error {
+	fn from(e: NulError) -> Self {
+		Self::InteriorNul(e)
 	}
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+impl Error {
+	/// Returns the SDK status code if this error was returned by an SDK call.
+	pub fn status(&self) -> Option<NvError> {
+		match self {
+			Self::Nv { status, .. } => Some(*status),
+			_ => None
+		}
+	}
+}
+
 #[inline]
-pub fn to_status(code: i32) -> Result<(), Error> {
-	if code == 0 { Ok(()) } else { Err(Error::Nv(NvError::from(code))) }
+pub(crate) fn to_status(function: &'static str, code: i32) -> Result<(), Error> {
+	if code == 0 {
+		Ok(())
+	} else {
+		Err(Error::Nv {
+			status: NvError::from(code),
+			function,
+			parameter: None
+		})
+	}
+}
+
+/// Like [`to_status`], but records the name of the parameter being accessed.
+///
+/// # Safety
+/// `parameter` must be null or point to a valid nul-terminated string.
+#[inline]
+pub(crate) unsafe fn to_param_status(function: &'static str, parameter: *const c_char, code: i32) -> Result<(), Error> {
+	if code == 0 {
+		Ok(())
+	} else {
+		Err(Error::Nv {
+			status: NvError::from(code),
+			function,
+			parameter: (!parameter.is_null()).then(|| unsafe { CStr::from_ptr(parameter) }.to_string_lossy().into_owned())
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Error, NvError, to_status};
+
+	#[test]
+	fn status_code_round_trip() {
+		for code in [-1, -7, -39, -98, -112, -309, -819, -1099] {
+			let status = NvError::from(code);
+			assert!(!matches!(status, NvError::Unknown(_)), "{code} should be a known status");
+			assert_eq!(status.code(), code);
+		}
+		assert_eq!(NvError::from(-7), NvError::Parameter);
+		assert_eq!(NvError::from(-1099), NvError::CUDA);
+	}
+
+	#[test]
+	fn unknown_status_code() {
+		assert_eq!(NvError::from(-12345), NvError::Unknown(-12345));
+		assert_eq!(NvError::Unknown(-12345).code(), -12345);
+		assert_eq!(NvError::from(1), NvError::Unknown(1));
+	}
+
+	#[test]
+	fn to_status_records_function() {
+		assert!(to_status("NvAR_Run", 0).is_ok());
+		match to_status("NvAR_Run", -15) {
+			Err(Error::Nv { status, function, parameter }) => {
+				assert_eq!(status, NvError::MissingInput);
+				assert_eq!(function, "NvAR_Run");
+				assert_eq!(parameter, None);
+			}
+			other => panic!("unexpected result: {other:?}")
+		}
+	}
 }
@@ -1,67 +1,59 @@
 pub mod ar;
 pub mod cv;
 mod error;
-
-use std::{
-    path::PathBuf,
-    sync::{Arc, OnceLock},
-};
+mod macros;
+mod runtime;
+mod version;
 
 use libloading::Library;
 
-pub use self::error::{Error, Result};
-
-pub(crate) static NVCV_DYLIB_PATH: OnceLock<Arc<PathBuf>> = OnceLock::new();
-pub(crate) static NVAR_DYLIB_PATH: OnceLock<Arc<PathBuf>> = OnceLock::new();
-pub(crate) static NVCV_LIBRARY: OnceLock<Arc<Library>> = OnceLock::new();
-pub(crate) static NVAR_LIBRARY: OnceLock<Arc<Library>> = OnceLock::new();
+pub use self::{
+	error::{Error, NvError, Result},
+	runtime::{Runtime, RuntimeBuilder},
+	version::Version
+};
 
-pub(crate) fn nvcv_path() -> &'static PathBuf {
-    NVCV_DYLIB_PATH.get_or_init(|| {
-        let path = match std::env::var("NVAR_ROOT") {
-            Ok(s) if !s.is_empty() => PathBuf::from(s).join("NVCVImage.dll"),
-            #[cfg(target_os = "windows")]
-            _ => "NVCVImage.dll".into(),
-        };
-        Arc::new(path)
-    })
+/// Returns a handle to the nvCV library of the [global runtime](Runtime::global), loading it if it has not been loaded
+/// yet.
+///
+/// Unlike [`nvcv_lib_handle`], this returns an [`Error::LoadLibrary`] if the library could not be loaded. A failed load
+/// is not cached, so subsequent calls will attempt to load the library again.
+#[inline]
+pub fn try_nvcv_lib_handle() -> Result<&'static Library> {
+	Ok(Runtime::global()?.nvcv_library())
 }
 
-pub(crate) fn nvar_path() -> &'static PathBuf {
-    NVAR_DYLIB_PATH.get_or_init(|| {
-        let path = match std::env::var("NVAR_ROOT") {
-            Ok(s) if !s.is_empty() => PathBuf::from(s).join("nvARPose.dll"),
-            #[cfg(target_os = "windows")]
-            _ => "nvARPose.dll".into(),
-        };
-        Arc::new(path)
-    })
+/// Returns a handle to the nvAR library of the [global runtime](Runtime::global), loading it if it has not been loaded
+/// yet.
+///
+/// Unlike [`nvar_lib_handle`], this returns an [`Error::LoadLibrary`] if the library could not be loaded. A failed load
+/// is not cached, so subsequent calls will attempt to load the library again.
+#[inline]
+pub fn try_nvar_lib_handle() -> Result<&'static Library> {
+	Ok(Runtime::global()?.nvar_library())
 }
 
+/// Returns a handle to the nvCV library of the [global runtime](Runtime::global), loading it if it has not been loaded
+/// yet.
+///
+/// # Panics
+/// Panics if the library could not be loaded. See [`try_nvcv_lib_handle`] for a fallible alternative.
 #[inline]
-pub fn nvcv_lib_handle() -> &'static libloading::Library {
-    NVCV_LIBRARY.get_or_init(|| {
-        let path = nvcv_path();
-        let lib = unsafe { Library::new(path) }.unwrap_or_else(|e| {
-            panic!(
-                "An error occurred while attempting to load the nvCV binary at `{}`: {e}",
-                path.display()
-            )
-        });
-        Arc::new(lib)
-    })
+pub fn nvcv_lib_handle() -> &'static Library {
+	try_nvcv_lib_handle().unwrap_or_else(|e| panic!("{e}"))
 }
 
+/// Returns a handle to the nvAR library of the [global runtime](Runtime::global), loading it if it has not been loaded
+/// yet.
+///
+/// # Panics
+/// Panics if the library could not be loaded. See [`try_nvar_lib_handle`] for a fallible alternative.
 #[inline]
-pub fn nvar_lib_handle() -> &'static libloading::Library {
-    NVAR_LIBRARY.get_or_init(|| {
-        let path = nvar_path();
-        let lib = unsafe { Library::new(path) }.unwrap_or_else(|e| {
-            panic!(
-                "An error occurred while attempting to load the nvAR binary at `{}`: {e}",
-                path.display()
-            )
-        });
-        Arc::new(lib)
-    })
+pub fn nvar_lib_handle() -> &'static Library {
+	try_nvar_lib_handle().unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Returns the version of the nvAR SDK loaded by the [global runtime](Runtime::global).
+pub fn version() -> Result<Version> {
+	Runtime::global()?.version()
 }
@@ -0,0 +1,70 @@
+/// Defines a table of function pointers that is resolved from a [`Library`](libloading::Library) once, at load time.
+///
+/// Symbols listed before the `optional` block are required: `load` fails with
+/// [`Error::UnsupportedBySdk`](crate::Error) if any of them is missing. Each symbol gets an accessor method of the same
+/// name, which for optional symbols returns the same error if the symbol was not present in the loaded library.
+macro_rules! define_api {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$(fn $req:ident($($req_arg:ty),* $(,)?) $(-> $req_ret:ty)?;)*
+			optional {
+				$(fn $opt:ident($($opt_arg:ty),* $(,)?) $(-> $opt_ret:ty)?;)*
+			}
+		}
+	) => {
+		$crate::macros::define_api! {
+			@table
+			$(#[$attr])*
+			$vis struct $name {
+				$(fn $req($($req_arg),*) $(-> $req_ret)?;)*
+				$(fn $opt($($opt_arg),*) $(-> $opt_ret)?;)*
+			}
+			required [$($req)*]
+		}
+	};
+	(
+		@table
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$(fn $sym:ident($($arg:ty),*) $(-> $ret:ty)?;)*
+		}
+		required [$($req:ident)*]
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$($sym: Option<unsafe extern "C" fn($($arg),*) $(-> $ret)?>),*
+		}
+
+		impl $name {
+			/// # Safety
+			/// `library` must be the library this table describes; see [`libloading::Library::get`].
+			pub unsafe fn load(library: &libloading::Library) -> $crate::Result<Self> {
+				let table = $name {
+					$($sym: unsafe { library.get::<unsafe extern "C" fn($($arg),*) $(-> $ret)?>(stringify!($sym).as_bytes()) }.ok().map(|sym| *sym)),*
+				};
+				$(if table.$req.is_none() {
+					return Err($crate::Error::UnsupportedBySdk(stringify!($req)));
+				})*
+				Ok(table)
+			}
+
+			$(
+				#[inline]
+				pub fn $sym(&self) -> $crate::Result<unsafe extern "C" fn($($arg),*) $(-> $ret)?> {
+					self.$sym.ok_or($crate::Error::UnsupportedBySdk(stringify!($sym)))
+				}
+			)*
+		}
+
+		impl std::fmt::Debug for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.debug_struct(stringify!($name))
+					$(.field(stringify!($sym), &self.$sym.is_some()))*
+					.finish()
+			}
+		}
+	};
+}
+
+pub(crate) use define_api;
@@ -0,0 +1,292 @@
+use std::{
+	ffi::{CStr, OsStr},
+	path::{Path, PathBuf},
+	sync::{Arc, OnceLock}
+};
+
+use libloading::Library;
+
+use crate::{
+	Error, NvError, Result, Version,
+	ar::sys::{NvAR_Api, NvAR_GetVersion},
+	cv::sys::NvCVImage_Api
+};
+
+#[cfg(target_os = "windows")]
+const NVCV_LIBRARY_NAME: &str = "NVCVImage.dll";
+#[cfg(target_os = "windows")]
+const NVAR_LIBRARY_NAME: &str = "nvARPose.dll";
+#[cfg(not(target_os = "windows"))]
+const NVCV_LIBRARY_NAME: &str = "libNVCVImage.so";
+#[cfg(not(target_os = "windows"))]
+const NVAR_LIBRARY_NAME: &str = "libnvARPose.so";
+
+static GLOBAL_RUNTIME: OnceLock<Arc<Runtime>> = OnceLock::new();
+
+/// Directories that may contain the SDK libraries for an SDK installed at `root`.
+fn root_paths(root: &Path) -> Vec<PathBuf> {
+	vec![
+		#[cfg(not(target_os = "windows"))]
+		root.join("lib"),
+		root.to_path_buf(),
+	]
+}
+
+/// Directories searched for the SDK libraries when no explicit path is configured, in order of priority.
+fn search_paths() -> Vec<PathBuf> {
+	search_paths_from(std::env::var_os("NVAR_ROOT").as_deref(), std::env::var_os("LD_LIBRARY_PATH").as_deref())
+}
+
+#[cfg_attr(target_os = "windows", allow(unused_variables))]
+fn search_paths_from(nvar_root: Option<&OsStr>, ld_library_path: Option<&OsStr>) -> Vec<PathBuf> {
+	let mut paths = Vec::new();
+	if let Some(root) = nvar_root.filter(|s| !s.is_empty()) {
+		paths.extend(root_paths(Path::new(root)));
+	}
+	#[cfg(not(target_os = "windows"))]
+	{
+		if let Some(ld_path) = ld_library_path {
+			paths.extend(std::env::split_paths(ld_path).filter(|p| !p.as_os_str().is_empty()));
+		}
+		paths.push(PathBuf::from("/usr/local/ARSDK/lib"));
+	}
+	paths
+}
+
+/// Resolves the full path to the library named `name` in one of `dirs`.
+fn find_library(dirs: &[PathBuf], name: &str) -> Option<PathBuf> {
+	dirs.iter().map(|dir| dir.join(name)).find(|path| path.is_file())
+}
+
+/// Resolves the library named `name`. An explicit SDK root is never substituted with another installation: if the
+/// library isn't found under it, `root/<name>` is returned so that loading fails with that path. Otherwise, `name` is
+/// searched for in `search_paths`, falling back to the bare name (and thus the platform loader's own search rules).
+fn resolve_library(sdk_root: Option<&Path>, search_paths: &[PathBuf], name: &str) -> PathBuf {
+	match sdk_root {
+		Some(root) => find_library(&root_paths(root), name).unwrap_or_else(|| root.join(name)),
+		None => find_library(search_paths, name).unwrap_or_else(|| PathBuf::from(name))
+	}
+}
+
+fn load_library(path: &Path) -> Result<Library> {
+	unsafe { Library::new(path) }.map_err(|source| Error::LoadLibrary { path: path.to_path_buf(), source })
+}
+
+/// A loaded instance of the nvCV & nvAR libraries.
+///
+/// Every [`Image`](crate::cv::Image), [`CUDAStream`](crate::ar::CUDAStream), and feature holds a handle to the runtime
+/// it was created with, so multiple SDK installations can be used side by side in the same process. Most applications
+/// only need the [global runtime](Runtime::global), which is used by all constructors that don't take a runtime.
+#[derive(Debug)]
+pub struct Runtime {
+	nvcv_api: NvCVImage_Api,
+	nvar_api: NvAR_Api,
+	nvcv: Library,
+	nvar: Library,
+	nvcv_path: PathBuf,
+	nvar_path: PathBuf
+}
+
+impl Runtime {
+	pub fn builder() -> RuntimeBuilder {
+		RuntimeBuilder::default()
+	}
+
+	/// Returns the process-wide default runtime, loading it if it has not been loaded yet.
+	///
+	/// The libraries are searched for in `NVAR_ROOT`, then (on non-Windows platforms) `LD_LIBRARY_PATH` and
+	/// `/usr/local/ARSDK/lib`, before falling back to the platform's default library search path. A failed load is not
+	/// cached, so subsequent calls will attempt to load the libraries again.
+	pub fn global() -> Result<&'static Arc<Runtime>> {
+		if let Some(runtime) = GLOBAL_RUNTIME.get() {
+			return Ok(runtime);
+		}
+
+		let runtime = RuntimeBuilder::default().build()?;
+		// If another thread won the race, our libraries are dropped here; the OS refcounts loaded modules, so this is
+		// harmless.
+		Ok(GLOBAL_RUNTIME.get_or_init(|| runtime))
+	}
+
+	#[inline]
+	pub fn nvcv_path(&self) -> &Path {
+		&self.nvcv_path
+	}
+
+	#[inline]
+	pub fn nvar_path(&self) -> &Path {
+		&self.nvar_path
+	}
+
+	/// Returns the nvCV function table, resolved when the runtime was loaded.
+	#[inline]
+	pub fn nvcv_api(&self) -> &NvCVImage_Api {
+		&self.nvcv_api
+	}
+
+	/// Returns the nvAR function table, resolved when the runtime was loaded.
+	#[inline]
+	pub fn nvar_api(&self) -> &NvAR_Api {
+		&self.nvar_api
+	}
+
+	/// Returns the version of the loaded nvAR SDK.
+	pub fn version(&self) -> Result<Version> {
+		Ok(Version::from_packed(unsafe { NvAR_GetVersion(&self.nvar_api) }?))
+	}
+
+	/// Returns [`Error::UnsupportedSdkVersion`] if the loaded nvAR SDK is older than `required`.
+	pub fn require_version(&self, required: Version) -> Result<()> {
+		let found = self.version()?;
+		if found < required { Err(Error::UnsupportedSdkVersion { required, found }) } else { Ok(()) }
+	}
+
+	/// Looks up the SDK's own description of `status` via `NvCV_GetErrorStringFromCode`.
+	///
+	/// Returns `None` if the loaded SDK does not provide the lookup or has no message for the code.
+	pub fn error_string(&self, status: NvError) -> Option<&str> {
+		let sym = self.nvcv_api.NvCV_GetErrorStringFromCode().ok()?;
+		let ptr = unsafe { sym(status.code()) };
+		if ptr.is_null() {
+			return None;
+		}
+		unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+	}
+
+	#[inline]
+	pub fn nvcv_library(&self) -> &Library {
+		&self.nvcv
+	}
+
+	#[inline]
+	pub fn nvar_library(&self) -> &Library {
+		&self.nvar
+	}
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RuntimeBuilder {
+	sdk_root: Option<PathBuf>,
+	nvcv_path: Option<PathBuf>,
+	nvar_path: Option<PathBuf>
+}
+
+impl RuntimeBuilder {
+	/// Loads both libraries from the SDK installed at `root` instead of searching `NVAR_ROOT` & the default paths.
+	/// [`RuntimeBuilder::build`] fails with [`Error::LoadLibrary`] if a library isn't found under `root`.
+	pub fn with_sdk_root(mut self, root: impl Into<PathBuf>) -> Self {
+		self.sdk_root = Some(root.into());
+		self
+	}
+
+	/// Loads the nvCV library from exactly `path`. Takes precedence over [`RuntimeBuilder::with_sdk_root`].
+	pub fn with_nvcv_path(mut self, path: impl Into<PathBuf>) -> Self {
+		self.nvcv_path = Some(path.into());
+		self
+	}
+
+	/// Loads the nvAR library from exactly `path`. Takes precedence over [`RuntimeBuilder::with_sdk_root`].
+	pub fn with_nvar_path(mut self, path: impl Into<PathBuf>) -> Self {
+		self.nvar_path = Some(path.into());
+		self
+	}
+
+	/// Loads the libraries, returning [`Error::UnsupportedBySdk`] if either is missing an entry point this crate
+	/// requires.
+	pub fn build(self) -> Result<Arc<Runtime>> {
+		let sdk_root = self.sdk_root.as_deref();
+		let search_paths = if sdk_root.is_none() { search_paths() } else { Vec::new() };
+		let nvcv_path = self
+			.nvcv_path
+			.unwrap_or_else(|| resolve_library(sdk_root, &search_paths, NVCV_LIBRARY_NAME));
+		let nvar_path = self
+			.nvar_path
+			.unwrap_or_else(|| resolve_library(sdk_root, &search_paths, NVAR_LIBRARY_NAME));
+
+		let nvcv = load_library(&nvcv_path)?;
+		let nvar = load_library(&nvar_path)?;
+		let nvcv_api = unsafe { NvCVImage_Api::load(&nvcv) }?;
+		let nvar_api = unsafe { NvAR_Api::load(&nvar) }?;
+		Ok(Arc::new(Runtime {
+			nvcv_api,
+			nvar_api,
+			nvcv,
+			nvar,
+			nvcv_path,
+			nvar_path
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		ffi::OsStr,
+		fs,
+		path::{Path, PathBuf}
+	};
+
+	use super::{NVAR_LIBRARY_NAME, resolve_library, search_paths_from};
+	use crate::{Error, Runtime};
+
+	/// Creates an empty directory under the system temp directory, unique to this process & test.
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("nvar-runtime-{}-{name}", std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	fn touch(path: &Path) -> PathBuf {
+		fs::create_dir_all(path.parent().unwrap()).unwrap();
+		fs::write(path, []).unwrap();
+		path.to_path_buf()
+	}
+
+	#[test]
+	#[cfg(not(target_os = "windows"))]
+	fn search_order() {
+		let paths = search_paths_from(Some(OsStr::new("/sdk")), Some(OsStr::new("/a::/b")));
+		let expected = ["/sdk/lib", "/sdk", "/a", "/b", "/usr/local/ARSDK/lib"].map(PathBuf::from);
+		assert_eq!(paths, expected);
+		assert_eq!(search_paths_from(Some(OsStr::new("")), None), [PathBuf::from("/usr/local/ARSDK/lib")]);
+	}
+
+	#[test]
+	fn first_search_path_wins() {
+		let dir = temp_dir("search");
+		let first = touch(&dir.join("first").join(NVAR_LIBRARY_NAME));
+		touch(&dir.join("second").join(NVAR_LIBRARY_NAME));
+		let search_paths = [dir.join("missing"), dir.join("first"), dir.join("second")];
+		assert_eq!(resolve_library(None, &search_paths, NVAR_LIBRARY_NAME), first);
+		assert_eq!(resolve_library(None, &[], NVAR_LIBRARY_NAME), PathBuf::from(NVAR_LIBRARY_NAME));
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn explicit_root_takes_precedence() {
+		let dir = temp_dir("root");
+		let root = dir.join("root");
+		#[cfg(not(target_os = "windows"))]
+		let in_root = touch(&root.join("lib").join(NVAR_LIBRARY_NAME));
+		#[cfg(target_os = "windows")]
+		let in_root = touch(&root.join(NVAR_LIBRARY_NAME));
+		touch(&dir.join("search").join(NVAR_LIBRARY_NAME));
+		assert_eq!(resolve_library(Some(&root), &[dir.join("search")], NVAR_LIBRARY_NAME), in_root);
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn missing_root_does_not_fall_through() {
+		let dir = temp_dir("missing-root");
+		let root = dir.join("does-not-exist");
+		touch(&dir.join("search").join(NVAR_LIBRARY_NAME));
+		assert_eq!(resolve_library(Some(&root), &[dir.join("search")], NVAR_LIBRARY_NAME), root.join(NVAR_LIBRARY_NAME));
+
+		match Runtime::builder().with_sdk_root(&root).build() {
+			Err(Error::LoadLibrary { path, .. }) => assert!(path.starts_with(&root)),
+			other => panic!("expected a load error under the SDK root, got {other:?}")
+		}
+		fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// A version of the nvAR SDK, as reported by `NvAR_GetVersion`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+	pub major: u8,
+	pub minor: u8,
+	pub release: u8,
+	pub build: u8
+}
+
+impl Version {
+	/// Creates a version with a build number of 0, which compares less than or equal to every build of the release.
+	pub const fn new(major: u8, minor: u8, release: u8) -> Self {
+		Version { major, minor, release, build: 0 }
+	}
+
+	/// Unpacks a version in the SDK's `major.minor.release.build` format, with one byte per field starting from the
+	/// most significant.
+	pub const fn from_packed(packed: u32) -> Self {
+		let [major, minor, release, build] = packed.to_be_bytes();
+		Version { major, minor, release, build }
+	}
+}
+
+impl fmt::Display for Version {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{}.{}.{}", self.major, self.minor, self.release, self.build)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Version;
+
+	#[test]
+	fn ordering() {
+		assert!(Version::new(0, 8, 2) > Version::new(0, 8, 0));
+		assert!(Version::new(0, 8, 0) > Version::new(0, 7, 9));
+		assert!(Version::new(1, 0, 0) > Version::new(0, 255, 255));
+		assert!(Version::from_packed(0x00_08_02_01) > Version::new(0, 8, 2));
+		assert_eq!(Version::new(0, 8, 0), Version::new(0, 8, 0));
+	}
+
+	#[test]
+	fn unpacking() {
+		let version = Version::from_packed(0x01_08_02_07);
+		assert_eq!((version.major, version.minor, version.release, version.build), (1, 8, 2, 7));
+		assert_eq!(Version::from_packed(0x00_08_02_00), Version::new(0, 8, 2));
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(Version::from_packed(0x00_08_02_01).to_string(), "0.8.2.1");
+	}
+}